@@ -1,3 +1,5 @@
+use std::env;
+
 use clap::{Command, ColorChoice, crate_version, Arg};
 
 
@@ -5,11 +7,133 @@ use clap::{Command, ColorChoice, crate_version, Arg};
 
 // build_app clap 构建命令行参数用
 pub fn build_app() -> Command<'static> {
-    let clap_color_choice = ColorChoice::Auto;
+    let clap_color_choice = if env::var_os("NO_COLOR").is_none() {
+        ColorChoice::Auto
+    } else {
+        ColorChoice::Never
+    };
 
     let app = Command::new("fdx")
         .version(crate_version!())
         .color(clap_color_choice)
+        .arg(
+            Arg::new("hidden")
+                .long("hidden")
+                .short('H')
+                .overrides_with("hidden")
+                .help("Search hidden files and directories")
+                .long_help(
+                    "Include hidden directories and files in the search results (default: \
+                         hidden files and directories are skipped). Files and directories are \
+                         considered to be hidden if their name starts with a `.` sign (dot).",
+                ),
+        )
+        .arg(
+            Arg::new("no-ignore")
+                .long("no-ignore")
+                .short('I')
+                .overrides_with("no-ignore")
+                .help("Do not respect .(git|fdx)ignore files")
+                .long_help(
+                    "Show search results from files and directories that would otherwise be \
+                         ignored by '.gitignore', '.ignore', '.fdxignore', or the global ignore \
+                         file.",
+                ),
+        )
+        .arg(
+            Arg::new("no-ignore-vcs")
+                .long("no-ignore-vcs")
+                .overrides_with("no-ignore-vcs")
+                .hide_short_help(true)
+                .help("Do not respect .gitignore files")
+                .long_help(
+                    "Show search results from files and directories that would otherwise be \
+                         ignored by '.gitignore' files.",
+                ),
+        )
+        .arg(
+            Arg::new("no-require-git")
+                .long("no-require-git")
+                .overrides_with("no-require-git")
+                .hide_short_help(true)
+                .help("Respect .gitignore files outside of git repositories")
+                .long_help(
+                    "Do not require a git repository to respect gitignores. By default, fdx \
+                         will only respect global gitignore rules, .gitignore rules and local \
+                         exclude rules if it detects that you are searching inside a git \
+                         repository. This flag allows you to relax this restriction such that \
+                         fdx will respect all git related ignore rules regardless of whether \
+                         you're searching in a git repository or not.",
+                ),
+        )
+        .arg(
+            Arg::new("no-ignore-parent")
+                .long("no-ignore-parent")
+                .overrides_with("no-ignore-parent")
+                .hide_short_help(true)
+                .help("Do not respect .(git|fdx)ignore files in parent directories")
+                .long_help(
+                    "Show search results from files and directories that would otherwise be \
+                         ignored by '.gitignore', '.ignore', or '.fdxignore' files in parent \
+                         directories.",
+                ),
+        )
+        .arg(
+            Arg::new("no-global-ignore-file")
+                .long("no-global-ignore-file")
+                .hide(true)
+                .help("Do not respect the global ignore file")
+                .long_help("Do not respect the global ignore file ('~/.config/fdx/ignore')."),
+        )
+        .arg(
+            Arg::new("rg-alias-hidden-ignore")
+                .short('u')
+                .long("unrestricted")
+                .multiple_occurrences(true)
+                .hide_short_help(true)
+                .help("Alias for '--no-ignore', and '--hidden' when given twice")
+                .long_help(
+                    "Alias for '--no-ignore'. Can be repeated. '-uu' is an alias for \
+                         '--no-ignore --hidden'.",
+                ),
+        )
+        .arg(
+            Arg::new("follow")
+                .long("follow")
+                .short('L')
+                .alias("dereference")
+                .overrides_with_all(&["follow", "no-follow", "follow-roots"])
+                .help("Follow all symbolic links")
+                .long_help(
+                    "By default, fdx does not descend into symlinked directories, except for \
+                         search paths that are symlinks themselves. Using this flag, all symbolic \
+                         links are followed. Symlink loops are reported as errors.",
+                ),
+        )
+        .arg(
+            Arg::new("no-follow")
+                .long("no-follow")
+                .short('P')
+                .overrides_with_all(&["follow", "no-follow", "follow-roots"])
+                .hide_short_help(true)
+                .help("Never follow symbolic links")
+                .long_help(
+                    "Never follow symbolic links, not even if they are given as search paths. \
+                         Search paths that are symbolic links are skipped with an error message.",
+                ),
+        )
+        .arg(
+            Arg::new("follow-roots")
+                .long("follow-roots")
+                .overrides_with_all(&["follow", "no-follow", "follow-roots"])
+                .hide_short_help(true)
+                .help("Only follow symbolic links given as search paths (default)")
+                .long_help(
+                    "Only follow symbolic links that are given as search paths on the command \
+                         line, but no symbolic links found during the traversal. This is the \
+                         default and can be used to override '--follow' or '--no-follow'.",
+                ),
+        )
         .arg(
             Arg::new("glob")
                 .long("glob")
@@ -18,6 +142,58 @@ pub fn build_app() -> Command<'static> {
                 .help("Glob-based search (default: regular expression)")
                 .long_help("Perform a glob-based search instead of a regular expression search."),
         )
+        .arg(
+            Arg::new("full-path")
+                .long("full-path")
+                .short('p')
+                .overrides_with("full-path")
+                .help("Search full path (default: filename only)")
+                .long_help(
+                    "By default, the search pattern is only matched against the filename (or \
+                     directory name). Using this flag, the pattern is matched against the full \
+                     path, which is relative to the current directory unless --absolute-path \
+                     is used. Example:\n  \
+                       fdx --glob -p '**/.git/config'",
+                ),
+        )
+        .arg(
+            Arg::new("fixed-strings")
+                .long("fixed-strings")
+                .short('F')
+                .alias("literal")
+                .overrides_with("fixed-strings")
+                .hide_short_help(true)
+                .help("Treat pattern as literal string instead of regex")
+                .long_help(
+                    "Treat the pattern as a literal string instead of a regular expression. Note \
+                     that this also performs substring comparison. If you want to match on an \
+                     exact filename, consider using '--glob'.",
+                ),
+        )
+        .arg(
+            Arg::new("case-sensitive")
+                .long("case-sensitive")
+                .short('s')
+                .overrides_with_all(&["ignore-case", "case-sensitive"])
+                .help("Case-sensitive search (default: smart case)")
+                .long_help(
+                    "Perform a case-sensitive search. By default, fdx uses case-insensitive \
+                     searches, unless the pattern contains an uppercase character (smart \
+                     case).",
+                ),
+        )
+        .arg(
+            Arg::new("ignore-case")
+                .long("ignore-case")
+                .short('i')
+                .overrides_with_all(&["case-sensitive", "ignore-case"])
+                .help("Case-insensitive search (default: smart case)")
+                .long_help(
+                    "Perform a case-insensitive search. By default, fdx uses case-insensitive \
+                     searches, unless the pattern contains an uppercase character (smart \
+                     case).",
+                ),
+        )
         .arg(
             Arg::new("absolute-path")
                 .long("absolute-path")
@@ -51,7 +227,7 @@ pub fn build_app() -> Command<'static> {
         )
         .arg(
             Arg::new("path")
-                // .multiple_occurrences(true)
+                .multiple_occurrences(true)
                 .allow_invalid_utf8(true)
                 .help("the root directory for the filesystem search (optional)")
                 .long_help(
@@ -59,6 +235,467 @@ pub fn build_app() -> Command<'static> {
                          omitted, search the current working directory.",
                 ),
         )
+        .arg(
+            Arg::new("search-path")
+                .long("search-path")
+                .takes_value(true)
+                .conflicts_with("path")
+                .multiple_occurrences(true)
+                .hide_short_help(true)
+                .number_of_values(1)
+                .allow_invalid_utf8(true)
+                .help("Provide paths to search as an alternative to the positional <path>")
+                .long_help(
+                    "Provide paths to search as an alternative to the positional <path> \
+                         argument. Changes the usage to `fdx [OPTIONS] --search-path <path> \
+                         --search-path <path2> [<pattern>]`",
+                ),
+        )
+        .arg(
+            Arg::new("max-depth")
+                .long("max-depth")
+                .short('d')
+                .takes_value(true)
+                .value_name("depth")
+                .number_of_values(1)
+                .help("Set maximum search depth (default: none)")
+                .long_help(
+                    "Limit the directory traversal to a given depth. Directories below this \
+                         depth are never entered. By default, there is no limit on the search \
+                         depth.",
+                ),
+        )
+        .arg(
+            Arg::new("min-depth")
+                .long("min-depth")
+                .takes_value(true)
+                .value_name("depth")
+                .number_of_values(1)
+                .hide_short_help(true)
+                .help("Only show results starting at given depth")
+                .long_help(
+                    "Only show search results starting at the given depth. \
+                     See also: '--max-depth' and '--exact-depth'",
+                ),
+        )
+        .arg(
+            Arg::new("exact-depth")
+                .long("exact-depth")
+                .takes_value(true)
+                .value_name("depth")
+                .number_of_values(1)
+                .hide_short_help(true)
+                .conflicts_with_all(&["max-depth", "min-depth"])
+                .help("Only show results at exact given depth")
+                .long_help(
+                    "Only show search results at the exact given depth. This is an alias for \
+                     '--min-depth <depth> --max-depth <depth>'.",
+                ),
+        )
+        .arg(
+            Arg::new("prune")
+                .long("prune")
+                .hide_short_help(true)
+                .help("Do not traverse into matching directories")
+                .long_help(
+                    "Do not traverse into directories that match the search criteria. The \
+                     matching directory itself is still shown. If you want to exclude specific \
+                     directories, use the '--exclude=…' option.",
+                ),
+        )
+        .arg(
+            Arg::new("extension")
+                .long("extension")
+                .short('e')
+                .multiple_occurrences(true)
+                .number_of_values(1)
+                .takes_value(true)
+                .value_name("ext")
+                .help("Filter by file extension")
+                .long_help(
+                    "(Additionally) filter search results by their file extension. Multiple \
+                     allowable file extensions can be specified. Extensions are matched \
+                     case-insensitively and may consist of several parts, like 'tar.gz'.\n\
+                     If you want to search for files without extension, \
+                     you can use the regex '^[^.]+$' as a normal search pattern.",
+                ),
+        )
+        .arg(
+            Arg::new("file-type")
+                .long("type")
+                .short('t')
+                .multiple_occurrences(true)
+                .number_of_values(1)
+                .takes_value(true)
+                .value_name("filetype")
+                .possible_values([
+                    "f", "file", "d", "directory", "l", "symlink", "x", "executable", "e", "empty",
+                    "s", "socket", "p", "pipe", "b", "block-device", "c", "char-device",
+                ])
+                .hide_possible_values(true)
+                .help(
+                    "Filter by type: file (f), directory (d), symlink (l),\nexecutable (x), \
+                         empty (e), socket (s), pipe (p),\nblock-device (b), char-device (c)",
+                )
+                .long_help(
+                    "Filter the search by type:\n  \
+                       'f' or 'file':         regular files\n  \
+                       'd' or 'directory':    directories\n  \
+                       'l' or 'symlink':      symbolic links\n  \
+                       's' or 'socket':       socket\n  \
+                       'p' or 'pipe':         named pipe (FIFO)\n  \
+                       'b' or 'block-device': block device\n  \
+                       'c' or 'char-device':  character device\n\n  \
+                       'x' or 'executable':   executables (for the current user)\n  \
+                       'e' or 'empty':        empty files or directories\n\n\
+                     This option can be specified more than once to include multiple file types. \
+                     Searching for '--type file --type symlink' will show both regular files as \
+                     well as symlinks. Note that the 'executable' and 'empty' filters work differently: \
+                     '--type executable' implies '--type file' by default. And '--type empty' searches \
+                     for empty files and directories, unless either '--type file' or '--type directory' \
+                     is specified in addition.\n\n\
+                     Examples:\n  \
+                       - Only search for files:\n      \
+                           fdx --type file …\n      \
+                           fdx -tf …\n  \
+                       - Find both files and symlinks\n      \
+                           fdx --type file --type symlink …\n      \
+                           fdx -tf -tl …\n  \
+                       - Find executable files:\n      \
+                           fdx --type executable\n      \
+                           fdx -tx\n  \
+                       - Find empty files:\n      \
+                           fdx --type empty --type file\n      \
+                           fdx -te -tf\n  \
+                       - Find empty directories:\n      \
+                           fdx --type empty --type directory\n      \
+                           fdx -te -td",
+                ),
+        )
+        .arg(
+            Arg::new("broken")
+                .long("broken")
+                .help("Only show broken symbolic links")
+                .long_help(
+                    "Only show symbolic links whose target does not exist. This works with and \
+                         without '--follow'. When following links, broken symbolic links are \
+                         reported as regular search results instead of errors.",
+                ),
+        )
+        .arg(
+            Arg::new("size")
+                .long("size")
+                .short('S')
+                .takes_value(true)
+                .number_of_values(1)
+                .allow_hyphen_values(true)
+                .multiple_occurrences(true)
+                .help("Limit results based on the size of files")
+                .long_help(
+                    "Limit results based on the size of files using the format <+-><NUM><UNIT>.\n   \
+                        '+': file size must be greater than or equal to this\n   \
+                        '-': file size must be less than or equal to this\n\
+                     If neither '+' nor '-' is specified, file size must be exactly equal to this.\n   \
+                        'NUM':  The numeric size (e.g. 500)\n   \
+                        'UNIT': The units for NUM. They are not case-sensitive.\n\
+                     Allowed unit values:\n    \
+                         'b':  bytes\n    \
+                         'k':  kilobytes (base ten, 10^3 = 1000 bytes)\n    \
+                         'm':  megabytes\n    \
+                         'g':  gigabytes\n    \
+                         't':  terabytes\n    \
+                         'ki': kibibytes (base two, 2^10 = 1024 bytes)\n    \
+                         'mi': mebibytes\n    \
+                         'gi': gibibytes\n    \
+                         'ti': tebibytes\n\
+                     Multiple size constraints are combined, e.g. '--size +10m --size -1g' \
+                     only shows files between 10 MB and 1 GB. Directories are never shown \
+                     when a size constraint is given.",
+                ),
+        )
+        .arg(
+            Arg::new("changed-within")
+                .long("changed-within")
+                .alias("change-newer-than")
+                .alias("newer-than")
+                .takes_value(true)
+                .value_name("date|dur")
+                .number_of_values(1)
+                .help("Filter by modification time or --time-kind (newer than)")
+                .long_help(
+                    "Filter results based on the file modification time, or on the timestamp selected \
+                     with '--time-kind' (access, status change or creation time). The argument can be provided \
+                     as a specific point in time (YYYY-MM-DD HH:MM:SS, YYYY-MM-DD or RFC 3339) or as \
+                     a duration (10h, 1d, 35min). \
+                     '--change-newer-than' or '--newer-than' can be used as aliases.\n\
+                     Examples:\n    \
+                         --changed-within 2weeks\n    \
+                         --change-newer-than '2018-10-27 10:00:00'\n    \
+                         --newer-than 2018-10-27",
+                ),
+        )
+        .arg(
+            Arg::new("changed-before")
+                .long("changed-before")
+                .alias("change-older-than")
+                .alias("older-than")
+                .takes_value(true)
+                .value_name("date|dur")
+                .number_of_values(1)
+                .help("Filter by modification time or --time-kind (older than)")
+                .long_help(
+                    "Filter results based on the file modification time, or on the timestamp selected \
+                     with '--time-kind' (access, status change or creation time). The argument can be provided \
+                     as a specific point in time (YYYY-MM-DD HH:MM:SS, YYYY-MM-DD or RFC 3339) or as \
+                     a duration (10h, 1d, 35min). \
+                     '--change-older-than' or '--older-than' can be used as aliases.\n\
+                     Examples:\n    \
+                         --changed-before '2018-10-27 10:00:00'\n    \
+                         --change-older-than 2weeks\n    \
+                         --older-than 2018-10-27",
+                ),
+        )
+        .arg(
+            Arg::new("newer")
+                .long("newer")
+                .takes_value(true)
+                .value_name("file")
+                .number_of_values(1)
+                .allow_invalid_utf8(true)
+                .help("Filter by modification time or --time-kind (newer than a reference file)")
+                .long_help(
+                    "Only show entries that have been modified more recently than the given \
+                     reference file. With '--time-kind', the selected timestamp of both is \
+                     compared instead.\n\
+                     Example:\n    \
+                         --newer Cargo.lock",
+                ),
+        )
+        .arg(
+            Arg::new("time-kind")
+                .long("time-kind")
+                .takes_value(true)
+                .value_name("kind")
+                .number_of_values(1)
+                .possible_values(["mtime", "atime", "ctime", "btime"])
+                .hide_possible_values(true)
+                .hide_short_help(true)
+                .help("Timestamp used by the time filters: mtime, atime, ctime, btime")
+                .long_help(
+                    "Select the timestamp that '--changed-within', '--changed-before' and \
+                     '--newer' compare against:\n  \
+                       'mtime': last modification time (default)\n  \
+                       'atime': last access time\n  \
+                       'ctime': last status change time\n  \
+                       'btime': creation (birth) time, if supported by the file system",
+                ),
+        )
+        .arg(
+            Arg::new("owner")
+                .long("owner")
+                .short('o')
+                .takes_value(true)
+                .value_name("user:group")
+                .number_of_values(1)
+                .help("Filter by owning user and/or group")
+                .long_help(
+                    "Filter files by their user and/or group. \
+                     Format: [(user|uid)][:(group|gid)]. Either side is optional. \
+                     Precede either side with a '!' to exclude files instead.\n\
+                     Examples:\n    \
+                         --owner john\n    \
+                         --owner :students\n    \
+                         --owner '!john:students'",
+                ),
+        )
+        .arg(
+            Arg::new("perm")
+                .long("perm")
+                .takes_value(true)
+                .value_name("mode")
+                .number_of_values(1)
+                .allow_hyphen_values(true)
+                .help("Filter by permission bits")
+                .long_help(
+                    "Filter files by their permission bits, like 'find -perm'. The mode can be \
+                     given in octal (644) or symbolic (u+x,g-w) notation. Symbolic modes are \
+                     applied to an initial mode of zero.\n   \
+                        'mode':  the permission bits must be exactly equal to mode\n   \
+                        '-mode': all of the bits in mode must be set\n   \
+                        '/mode': any of the bits in mode must be set\n\
+                     Examples:\n    \
+                         --perm 644\n    \
+                         --perm /o+w\n    \
+                         --perm -u+x",
+                ),
+        )
+        .arg(
+            Arg::new("exclude")
+                .long("exclude")
+                .short('E')
+                .takes_value(true)
+                .value_name("pattern")
+                .number_of_values(1)
+                .multiple_occurrences(true)
+                .help("Exclude entries that match the given glob pattern")
+                .long_help(
+                    "Exclude files/directories that match the given glob pattern. This \
+                         overrides any other ignore logic. Matching directories are not \
+                         descended into. Multiple exclude patterns can be specified.\n\n\
+                         Examples:\n  \
+                           --exclude '*.pyc'\n  \
+                           --exclude node_modules",
+                ),
+        )
+        .arg(
+            Arg::new("ignore-file")
+                .long("ignore-file")
+                .takes_value(true)
+                .value_name("path")
+                .number_of_values(1)
+                .multiple_occurrences(true)
+                .allow_invalid_utf8(true)
+                .hide_short_help(true)
+                .help("Add custom ignore-file in '.gitignore' format")
+                .long_help(
+                    "Add a custom ignore-file in '.gitignore' format. These files have a low \
+                         precedence.",
+                ),
+        )
+        .arg(
+            Arg::new("exclude-from")
+                .long("exclude-from")
+                .takes_value(true)
+                .value_name("path")
+                .number_of_values(1)
+                .multiple_occurrences(true)
+                .allow_invalid_utf8(true)
+                .hide_short_help(true)
+                .help("Read exclude patterns from a file")
+                .long_help(
+                    "Read glob patterns to exclude from the given file, one pattern per line. \
+                         Empty lines and lines starting with '#' are skipped. The patterns are \
+                         treated exactly like patterns passed via '--exclude'.",
+                ),
+        )
+        .arg(
+            Arg::new("null_separator")
+                .long("print0")
+                .short('0')
+                .overrides_with("null_separator")
+                .hide_short_help(true)
+                .help("Separate results by the null character")
+                .long_help(
+                    "Separate search results by the null character (instead of newlines). \
+                         Useful for piping results to 'xargs -0', as it is safe for any file \
+                         name, including ones that contain newlines.",
+                ),
+        )
+        .arg(
+            Arg::new("path-separator")
+                .long("path-separator")
+                .takes_value(true)
+                .value_name("separator")
+                .number_of_values(1)
+                .hide_short_help(true)
+                .help("Set path separator when printing file paths")
+                .long_help(
+                    "Set the path separator to use when printing file paths. The default is \
+                         the OS-specific separator ('/' on Unix). This also applies to absolute \
+                         paths.",
+                ),
+        )
+        .arg(
+            Arg::new("strip-cwd-prefix")
+                .long("strip-cwd-prefix")
+                .takes_value(true)
+                .value_name("when")
+                .min_values(0)
+                .require_equals(true)
+                .default_missing_value("always")
+                .possible_values(["auto", "always", "never"])
+                .hide_possible_values(true)
+                .hide_short_help(true)
+                .help("When to strip the './' prefix from results: *auto*, always, never")
+                .long_help(
+                    "Control the './' prefix of relative results when searching the current \
+                     directory:\n  \
+                       'auto':   strip the prefix if the output goes to an interactive terminal (default)\n  \
+                       'always': always strip the prefix, '--strip-cwd-prefix' is short for this\n  \
+                       'never':  never strip the prefix\n\
+                     Explicitly given search paths are always shown as they were passed.",
+                ),
+        )
+        .arg(
+            Arg::new("color")
+                .long("color")
+                .short('c')
+                .takes_value(true)
+                .value_name("when")
+                .possible_values(["never", "auto", "always"])
+                .hide_possible_values(true)
+                .help("When to use colors: never, *auto*, always")
+                .long_help(
+                    "Declare when to use color for the pattern match output:\n  \
+                       'auto':      show colors if the output goes to an interactive console (default)\n  \
+                       'never':     do not use colorized output\n  \
+                       'always':    always use colorized output\n\
+                     Colors are taken from the 'LS_COLORS' environment variable, with a built-in \
+                     default theme if it is not set. 'auto' does not use colors if the \
+                     'NO_COLOR' environment variable is set.",
+                ),
+        )
+        .arg(
+            Arg::new("threads")
+                .long("threads")
+                .short('j')
+                .takes_value(true)
+                .value_name("num")
+                .number_of_values(1)
+                .hide_short_help(true)
+                .help("Set number of threads")
+                .long_help(
+                    "Set number of threads to use for searching (default: number of available \
+                         CPU cores, limited by the CPU quota of the current cgroup). Use '-j1' \
+                         for a single-threaded search with a deterministic output order.",
+                ),
+        )
+        .arg(
+            Arg::new("max-results")
+                .long("max-results")
+                .takes_value(true)
+                .value_name("count")
+                .number_of_values(1)
+                .hide_short_help(true)
+                .help("Limit number of search results")
+                .long_help("Limit the number of search results to 'count' and quit immediately."),
+        )
+        .arg(
+            Arg::new("max-one-result")
+                .short('1')
+                .hide_short_help(true)
+                .overrides_with("max-results")
+                .help("Limit search to a single result")
+                .long_help(
+                    "Limit the search to a single result and quit immediately. \
+                     This is an alias for '--max-results=1'.",
+                ),
+        )
+        .arg(
+            Arg::new("quiet")
+                .long("quiet")
+                .short('q')
+                .alias("has-results")
+                .hide_short_help(true)
+                .conflicts_with_all(&["max-results", "max-one-result"])
+                .help("Print nothing, exit code 0 if match found, 1 otherwise")
+                .long_help(
+                    "When the flag is present, the program does not produce any output and will \
+                     return with an exit code of 0 if there is at least one match. Otherwise, the \
+                     exit code will be 1. The search stops at the first match. \
+                     '--has-results' can be used as an alias.",
+                ),
+        )
         .arg(
             Arg::new("base-directory")
                 .long("base-directory")
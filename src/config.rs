@@ -1,5 +1,127 @@
+use std::path::PathBuf;
 
+use lscolors::LsColors;
+use regex::bytes::RegexSet;
 
-pub struct Config  {
-    
-}
\ No newline at end of file
+use crate::filetypes::FileTypes;
+#[cfg(unix)]
+use crate::filter::{OwnerFilter, PermFilter};
+use crate::filter::{SizeFilter, TimeFilter, TimeKind};
+
+/// How symbolic links are treated during the traversal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SymlinkPolicy {
+    /// Never follow symbolic links, not even if they are given as search paths.
+    Never,
+    /// Only follow symbolic links that are given as search paths.
+    Roots,
+    /// Follow all symbolic links.
+    Always,
+}
+
+/// Configuration options for *fdx*.
+pub struct Config {
+    /// Whether the search is case-sensitive or case-insensitive.
+    pub case_sensitive: bool,
+
+    /// Whether to search within the full file path or just the base name (filename or directory
+    /// name).
+    pub search_full_path: bool,
+
+    /// Whether to ignore hidden files and directories (or not).
+    pub ignore_hidden: bool,
+
+    /// Whether to respect `.ignore` and `.fdxignore` files or not.
+    pub read_ignore: bool,
+
+    /// Whether to respect ignore files in parent directories or not.
+    pub read_parent_ignore: bool,
+
+    /// Whether to respect VCS ignore files (`.gitignore`, ..) or not.
+    pub read_vcsignore: bool,
+
+    /// Whether to require a `.git` directory to respect gitignore files.
+    pub require_git_to_read_vcsignore: bool,
+
+    /// Whether to respect the global ignore files (git's global excludes and
+    /// `~/.config/fdx/ignore`) or not.
+    pub read_global_ignore: bool,
+
+    /// Which symbolic links to follow.
+    pub symlink_policy: SymlinkPolicy,
+
+    /// `None` if the output should not be colorized. Otherwise, a `LsColors` instance that defines
+    /// how to style different filetypes.
+    pub ls_colors: Option<LsColors>,
+
+    /// Whether or not we are writing to an interactive terminal
+    pub interactive_terminal: bool,
+
+    /// Whether elements of output should be separated by a null character
+    pub null_separator: bool,
+
+    /// The maximum search depth, or `None` if no maximum search depth should be set.
+    ///
+    /// A depth of `1` includes all files under the current directory, a depth of `2` also includes
+    /// all files under subdirectories of the current directory, etc.
+    pub max_depth: Option<usize>,
+
+    /// The minimum depth for reported entries, or `None`.
+    pub min_depth: Option<usize>,
+
+    /// Whether to stop traversing into matching directories.
+    pub prune: bool,
+
+    /// The number of threads to use.
+    pub threads: usize,
+
+    /// If true, the program doesn't print anything and will instead return an exit code of 0
+    /// if there's at least one match. Otherwise, the exit code will be 1.
+    pub quiet: bool,
+
+    /// The separator used to print file paths.
+    pub path_separator: Option<String>,
+
+    /// The actual separator, either the system default separator or `path_separator`
+    pub actual_path_separator: String,
+
+    /// Whether or not to strip the './' prefix for search results
+    pub strip_cwd_prefix: bool,
+
+    /// The maximum number of search results
+    pub max_results: Option<usize>,
+
+    /// Whether to only show symbolic links whose target does not exist.
+    pub broken_symlinks_only: bool,
+
+    /// The type of file to search for. If set to `None`, all file types are displayed. If
+    /// set to `Some(..)`, only the types that are specified are shown.
+    pub file_types: Option<FileTypes>,
+
+    /// The extensions to search for. Only entries matching one of the extensions will be
+    /// included. The patterns in the set are case-insensitive.
+    pub extensions: Option<RegexSet>,
+
+    /// A list of glob patterns that should be excluded from the search.
+    pub exclude_patterns: Vec<String>,
+
+    /// A list of custom ignore files.
+    pub ignore_files: Vec<PathBuf>,
+
+    /// The given constraints on the size of returned files
+    pub size_constraints: Vec<SizeFilter>,
+
+    /// Constraints on the timestamps of files, see `time_kind`.
+    pub time_constraints: Vec<TimeFilter>,
+
+    /// The timestamp of an entry that `time_constraints` are checked against.
+    pub time_kind: TimeKind,
+
+    #[cfg(unix)]
+    /// User/group ownership constraint
+    pub owner_constraint: Option<OwnerFilter>,
+
+    #[cfg(unix)]
+    /// Permission bits constraint
+    pub perm_constraint: Option<PermFilter>,
+}
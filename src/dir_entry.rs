@@ -6,10 +6,11 @@ use std::{
 use once_cell::unsync::OnceCell;
 
 use crate::config::Config;
+use crate::filesystem::strip_current_dir;
 
 enum DirEntryInner {
     Normal(ignore::DirEntry),
-    BrokenSymlink(PathBuf),
+    BrokenSymlink { path: PathBuf, depth: usize },
 }
 
 pub struct DirEntry {
@@ -26,9 +27,9 @@ impl DirEntry {
         }
     }
 
-    pub fn broken_symlink(path: PathBuf) -> Self {
+    pub fn broken_symlink(path: PathBuf, depth: usize) -> Self {
         Self {
-            inner: DirEntryInner::BrokenSymlink(path),
+            inner: DirEntryInner::BrokenSymlink { path, depth },
             metadata: OnceCell::new(),
         }
     }
@@ -36,41 +37,33 @@ impl DirEntry {
     pub fn path(&self) -> &Path {
         match &self.inner {
             DirEntryInner::Normal(e) => e.path(),
-            DirEntryInner::BrokenSymlink(pathbuf) => pathbuf.as_path(),
+            DirEntryInner::BrokenSymlink { path, .. } => path.as_path(),
         }
     }
 
-    pub fn into_path(self) -> PathBuf {
-        match self.inner {
-            DirEntryInner::Normal(e) => e.into_path(),
-            DirEntryInner::BrokenSymlink(p) => p,
-        }
-    }
-
-    /// Returns the path as it should be presented to the user.
-pub fn stripped_path(&self) -> &Path {
-        self.path()
-        // if config.strip_cwd_prefix {
-        //     strip_current_dir(self.path())
-        // } else {
-        //     self.path()
-        // }
-    }
-
     /// Returns the path as it should be presented to the user.
-    pub fn into_stripped_path(self, config: &Config) -> PathBuf {
-        self.into_path()
-        // if config.strip_cwd_prefix {
-        //     self.stripped_path(config).to_path_buf()
-        // } else {
-        //     self.into_path()
-        // }
+    pub fn stripped_path(&self, config: &Config) -> &Path {
+        if config.strip_cwd_prefix {
+            strip_current_dir(self.path())
+        } else {
+            self.path()
+        }
     }
 
     pub fn file_type(&self) -> Option<FileType> {
         match &self.inner {
             DirEntryInner::Normal(e) => e.file_type(),
-            DirEntryInner::BrokenSymlink(_) => self.metadata().map(|m| m.file_type()),
+            DirEntryInner::BrokenSymlink { .. } => self.metadata().map(|m| m.file_type()),
+        }
+    }
+
+    /// Whether this entry is a symbolic link whose target does not exist.
+    pub fn is_broken_symlink(&self) -> bool {
+        match &self.inner {
+            DirEntryInner::Normal(e) => {
+                e.file_type().is_some_and(|ft| ft.is_symlink()) && e.path().metadata().is_err()
+            }
+            DirEntryInner::BrokenSymlink { .. } => true,
         }
     }
 
@@ -78,7 +71,7 @@ pub fn stripped_path(&self) -> &Path {
         self.metadata
             .get_or_init(|| match &self.inner {
                 DirEntryInner::Normal(e) => e.metadata().ok(),
-                DirEntryInner::BrokenSymlink(path) => path.symlink_metadata().ok(),
+                DirEntryInner::BrokenSymlink { path, .. } => path.symlink_metadata().ok(),
             })
             .as_ref()
     }
@@ -86,7 +79,7 @@ pub fn stripped_path(&self) -> &Path {
     pub fn depth(&self) -> Option<usize> {
         match &self.inner {
             DirEntryInner::Normal(e) => Some(e.depth()),
-            DirEntryInner::BrokenSymlink(_) => None,
+            DirEntryInner::BrokenSymlink { depth, .. } => Some(*depth),
         }
     }
 }
@@ -102,7 +95,7 @@ impl Eq for DirEntry {}
 impl PartialOrd for DirEntry {
     #[inline]
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.path().partial_cmp(other.path())
+        Some(self.cmp(other))
     }
 }
 
@@ -25,10 +25,6 @@ impl From<ExitCode> for i32 {
 }
 
 impl ExitCode {
-    fn is_error(self) -> bool {
-        i32::from(self) != 0
-    }
-
     /// Exit the process with the appropriate code.
     pub fn exit(self) -> ! {
         #[cfg(unix)]
@@ -1,8 +1,14 @@
-use std::{path::{Path, PathBuf}, io, env};
+use std::{path::{Component, Path, PathBuf}, io, env, fs};
+use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::borrow::Cow;
+#[cfg(unix)]
+use std::os::unix::fs::{FileTypeExt, MetadataExt};
 
 use normpath::PathExt;
+use once_cell::sync::Lazy;
+
+use crate::dir_entry;
 
 
 
@@ -37,13 +43,233 @@ pub fn absolute_path(path: &Path) -> io::Result<PathBuf> {
     Ok(path_buf)
 }
 
+/// The effective user ID and all group IDs of the current process, used for permission checks.
+#[cfg(unix)]
+static CURRENT_USER: Lazy<(u32, Vec<u32>)> = Lazy::new(|| {
+    let mut groups: Vec<u32> = users::group_access_list()
+        .map(|groups| groups.iter().map(|g| g.gid()).collect())
+        .unwrap_or_default();
+    groups.push(users::get_effective_gid());
+    (users::get_effective_uid(), groups)
+});
+
+/// Whether the current user is allowed to execute the file described by `md`.
+#[cfg(unix)]
+pub fn is_executable(md: &fs::Metadata) -> bool {
+    let mode = md.mode();
+    let (euid, groups) = &*CURRENT_USER;
+    if *euid == 0 {
+        mode & 0o111 != 0
+    } else if md.uid() == *euid {
+        mode & 0o100 != 0
+    } else if groups.contains(&md.gid()) {
+        mode & 0o010 != 0
+    } else {
+        mode & 0o001 != 0
+    }
+}
+
+pub fn is_empty(entry: &dir_entry::DirEntry) -> bool {
+    if let Some(file_type) = entry.file_type() {
+        if file_type.is_dir() {
+            if let Ok(mut entries) = fs::read_dir(entry.path()) {
+                entries.next().is_none()
+            } else {
+                false
+            }
+        } else if file_type.is_file() {
+            entry.metadata().map(|m| m.len() == 0).unwrap_or(false)
+        } else {
+            false
+        }
+    } else {
+        false
+    }
+}
+
+#[cfg(unix)]
+pub fn is_socket(ft: fs::FileType) -> bool {
+    ft.is_socket()
+}
+
+#[cfg(unix)]
+pub fn is_pipe(ft: fs::FileType) -> bool {
+    ft.is_fifo()
+}
+
+#[cfg(unix)]
+pub fn is_block_device(ft: fs::FileType) -> bool {
+    ft.is_block_device()
+}
+
+#[cfg(unix)]
+pub fn is_char_device(ft: fs::FileType) -> bool {
+    ft.is_char_device()
+}
+
+/// The user's configuration directory: `$XDG_CONFIG_HOME`, or `~/.config` as a fallback.
+pub fn config_dir() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| {
+            env::var_os("HOME")
+                .filter(|home| !home.is_empty())
+                .map(|home| PathBuf::from(home).join(".config"))
+        })
+}
+
+/// The number of CPUs the current cgroup (v2) is allowed to use, based on `cpu.max`.
+///
+/// Returns `None` if there is no quota or it can not be determined.
+#[cfg(target_os = "linux")]
+pub fn cgroup_cpu_limit() -> Option<usize> {
+    let content = fs::read_to_string("/sys/fs/cgroup/cpu.max").ok()?;
+    parse_cpu_max(&content)
+}
+
+/// Parses the content of a cgroup v2 `cpu.max` file ("$MAX $PERIOD") into a number of CPUs,
+/// rounded up. Returns `None` for "max", i.e. no quota.
+#[cfg(any(target_os = "linux", test))]
+fn parse_cpu_max(content: &str) -> Option<usize> {
+    let mut fields = content.split_whitespace();
+    let quota: u64 = fields.next()?.parse().ok()?;
+    let period: u64 = fields.next().unwrap_or("100000").parse().ok()?;
+    if quota == 0 || period == 0 {
+        return None;
+    }
+    Some(quota.div_ceil(period) as usize)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn cgroup_cpu_limit() -> Option<usize> {
+    None
+}
+
+/// Normalize a path without touching the file system.
+///
+/// The path is made absolute and `.` and `..` components are resolved lexically. Unlike
+/// `normpath`, symbolic links are not resolved, so a link and its target stay different paths.
+pub fn lexical_normalize(path: &Path) -> PathBuf {
+    let absolute = path_absolute_form(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut normalized = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Remove search paths that are duplicates of an earlier search path.
+///
+/// The order of the remaining paths is preserved. Paths are compared in their lexically
+/// normalized form, so `src`, `./src` and `src/../src` are all considered to be the same root.
+pub fn dedup_search_paths(search_paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    let mut seen = HashSet::new();
+    search_paths
+        .into_iter()
+        .filter(|path| seen.insert(lexical_normalize(path)))
+        .collect()
+}
+
+/// Returns the paths under which search paths that lie inside another search path show up in the
+/// walk of that outer search path.
+///
+/// Nested roots are still walked on their own, so that ignore rules of the outer root never hide
+/// their contents. The walk of the outer root must not descend into them instead. Entry paths
+/// always start with the search path they were found in, so they can be compared to the returned
+/// paths directly.
+pub fn nested_search_paths(search_paths: &[PathBuf]) -> Vec<PathBuf> {
+    let normalized: Vec<PathBuf> = search_paths.iter().map(|p| lexical_normalize(p)).collect();
+    let mut nested = Vec::new();
+    for (outer, outer_normalized) in search_paths.iter().zip(&normalized) {
+        for inner in &normalized {
+            if let Ok(relative) = inner.strip_prefix(outer_normalized) {
+                if !relative.as_os_str().is_empty() {
+                    nested.push(outer.join(relative));
+                }
+            }
+        }
+    }
+    nested
+}
+
 /// Remove the `./` prefix from a path.
 pub fn strip_current_dir(path: &Path) -> &Path {
     path.strip_prefix(".").unwrap_or(path)
 }
 
 #[cfg(any(unix, target_os = "redox"))]
-pub fn osstr_to_bytes(input: &OsStr) -> Cow<[u8]> {
+pub fn osstr_to_bytes(input: &OsStr) -> Cow<'_, [u8]> {
     use std::os::unix::ffi::OsStrExt;
     Cow::Borrowed(input.as_bytes())
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lexical_normalize_resolves_dots() {
+        let cwd = env::current_dir().unwrap();
+        assert_eq!(lexical_normalize(Path::new(".")), cwd);
+        assert_eq!(lexical_normalize(Path::new("./src")), cwd.join("src"));
+        assert_eq!(lexical_normalize(Path::new("src/../src/./bin")), cwd.join("src/bin"));
+        assert_eq!(lexical_normalize(Path::new("/a/b/../c")), PathBuf::from("/a/c"));
+    }
+
+    #[test]
+    fn cpu_max() {
+        assert_eq!(parse_cpu_max("max 100000"), None);
+        assert_eq!(parse_cpu_max("150000 100000"), Some(2));
+        assert_eq!(parse_cpu_max("50000 100000"), Some(1));
+        assert_eq!(parse_cpu_max("200000 100000\n"), Some(2));
+        assert_eq!(parse_cpu_max("300000"), Some(3));
+        assert_eq!(parse_cpu_max("1 0"), None);
+        assert_eq!(parse_cpu_max("0 100000"), None);
+        assert_eq!(parse_cpu_max(""), None);
+    }
+
+    #[test]
+    fn dedup_search_paths_keeps_first_of_duplicates() {
+        let paths = vec![
+            PathBuf::from("src"),
+            PathBuf::from("."),
+            PathBuf::from("./src"),
+            PathBuf::from("src/../src"),
+            PathBuf::from("tests"),
+        ];
+        assert_eq!(
+            dedup_search_paths(paths),
+            vec![PathBuf::from("src"), PathBuf::from("."), PathBuf::from("tests")]
+        );
+    }
+
+    #[test]
+    fn dedup_search_paths_keeps_nested_roots() {
+        let paths = vec![PathBuf::from("."), PathBuf::from("src"), PathBuf::from("src/bin")];
+        assert_eq!(dedup_search_paths(paths.clone()), paths);
+    }
+
+    #[test]
+    fn nested_search_paths_are_found() {
+        let paths = vec![PathBuf::from("src/bin"), PathBuf::from("."), PathBuf::from("./src")];
+        assert_eq!(
+            nested_search_paths(&paths),
+            vec![PathBuf::from("./src/bin"), PathBuf::from("./src"), PathBuf::from("./src/bin")]
+        );
+        assert!(nested_search_paths(&[PathBuf::from("src"), PathBuf::from("tests")]).is_empty());
+        assert!(nested_search_paths(&[PathBuf::from("src"), PathBuf::from("src-old")]).is_empty());
+    }
+
+    #[test]
+    fn nested_search_paths_keep_the_outer_prefix() {
+        let cwd = env::current_dir().unwrap();
+        let paths = vec![cwd.join("a/../b"), PathBuf::from("b/c")];
+        assert_eq!(nested_search_paths(&paths), vec![cwd.join("a/../b").join("c")]);
+    }
+}
@@ -0,0 +1,13 @@
+#[cfg(unix)]
+pub use self::owner::OwnerFilter;
+#[cfg(unix)]
+pub use self::perm::PermFilter;
+pub use self::size::SizeFilter;
+pub use self::time::{TimeFilter, TimeKind};
+
+#[cfg(unix)]
+mod owner;
+#[cfg(unix)]
+mod perm;
+mod size;
+mod time;
@@ -0,0 +1,146 @@
+use anyhow::{anyhow, Result};
+use std::fs;
+
+/// Filter on the owning user and group of an entry.
+///
+/// User and group names are resolved to numeric IDs once, when the filter is parsed, so matching
+/// an entry only compares the IDs from its metadata.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OwnerFilter {
+    uid: Check<u32>,
+    gid: Check<u32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Check<T> {
+    Equal(T),
+    NotEq(T),
+    Ignore,
+}
+
+impl OwnerFilter {
+    /// Parses an owner constraint
+    /// Returns an error if the string is invalid
+    /// Returns Ok(None) when string is acceptable but a noop (such as "" or ":")
+    pub fn from_string(input: &str) -> Result<Option<Self>> {
+        let mut it = input.split(':');
+        let (fst, snd) = (it.next(), it.next());
+
+        if it.next().is_some() {
+            return Err(anyhow!(
+                "more than one ':' present in owner string '{}'. See 'fdx --help'.",
+                input
+            ));
+        }
+
+        let uid = Check::parse(fst, |s| {
+            s.parse()
+                .ok()
+                .or_else(|| users::get_user_by_name(s).map(|user| user.uid()))
+                .ok_or_else(|| anyhow!("'{}' is not a recognized user name", s))
+        })?;
+        let gid = Check::parse(snd, |s| {
+            s.parse()
+                .ok()
+                .or_else(|| users::get_group_by_name(s).map(|group| group.gid()))
+                .ok_or_else(|| anyhow!("'{}' is not a recognized group name", s))
+        })?;
+
+        if let (Check::Ignore, Check::Ignore) = (uid, gid) {
+            Ok(None)
+        } else {
+            Ok(Some(OwnerFilter { uid, gid }))
+        }
+    }
+
+    pub fn matches(&self, md: &fs::Metadata) -> bool {
+        use std::os::unix::fs::MetadataExt;
+
+        self.uid.check(md.uid()) && self.gid.check(md.gid())
+    }
+}
+
+impl<T: PartialEq> Check<T> {
+    fn check(&self, v: T) -> bool {
+        match self {
+            Check::Equal(x) => v == *x,
+            Check::NotEq(x) => v != *x,
+            Check::Ignore => true,
+        }
+    }
+
+    fn parse<F>(s: Option<&str>, f: F) -> Result<Self>
+    where
+        F: Fn(&str) -> Result<T>,
+    {
+        let (s, equality) = match s {
+            Some("") | None => return Ok(Check::Ignore),
+            Some(s) => match s.strip_prefix('!') {
+                Some(negated) => (negated, false),
+                None => (s, true),
+            },
+        };
+
+        f(s).map(|x| {
+            if equality {
+                Check::Equal(x)
+            } else {
+                Check::NotEq(x)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owner(uid: Check<u32>, gid: Check<u32>) -> Option<OwnerFilter> {
+        Some(OwnerFilter { uid, gid })
+    }
+
+    #[test]
+    fn numeric_ids() {
+        use Check::*;
+        assert_eq!(OwnerFilter::from_string("1000").unwrap(), owner(Equal(1000), Ignore));
+        assert_eq!(OwnerFilter::from_string("1000:").unwrap(), owner(Equal(1000), Ignore));
+        assert_eq!(OwnerFilter::from_string(":1000").unwrap(), owner(Ignore, Equal(1000)));
+        assert_eq!(OwnerFilter::from_string("1:2").unwrap(), owner(Equal(1), Equal(2)));
+    }
+
+    #[test]
+    fn negated_ids() {
+        use Check::*;
+        assert_eq!(OwnerFilter::from_string("!0").unwrap(), owner(NotEq(0), Ignore));
+        assert_eq!(OwnerFilter::from_string(":!0").unwrap(), owner(Ignore, NotEq(0)));
+        assert_eq!(OwnerFilter::from_string("!1:!2").unwrap(), owner(NotEq(1), NotEq(2)));
+    }
+
+    #[test]
+    fn names_are_resolved() {
+        use Check::*;
+        if let Some(name) = users::get_current_username() {
+            let uid = users::get_current_uid();
+            let input = name.to_str().unwrap();
+            assert_eq!(OwnerFilter::from_string(input).unwrap(), owner(Equal(uid), Ignore));
+        }
+        if let Some(name) = users::get_current_groupname() {
+            let gid = users::get_current_gid();
+            let input = format!(":!{}", name.to_str().unwrap());
+            assert_eq!(OwnerFilter::from_string(&input).unwrap(), owner(Ignore, NotEq(gid)));
+        }
+    }
+
+    #[test]
+    fn noop_filters() {
+        assert_eq!(OwnerFilter::from_string("").unwrap(), None);
+        assert_eq!(OwnerFilter::from_string(":").unwrap(), None);
+    }
+
+    #[test]
+    fn invalid_filters() {
+        for input in ["1:2:3", "::", "no-such-user-fdx", ":no-such-group-fdx", "!"] {
+            assert!(OwnerFilter::from_string(input).is_err(), "{:?}", input);
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use anyhow::{anyhow, Result};
+use std::fs;
+
+/// Filter on the permission bits of an entry, modelled after `find -perm`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PermFilter {
+    mode: u32,
+    kind: PermMatch,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PermMatch {
+    /// The permission bits have to be exactly equal to the given mode.
+    Exact,
+    /// All of the given bits have to be set (`-mode`).
+    AllOf,
+    /// Any of the given bits has to be set (`/mode`).
+    AnyOf,
+}
+
+const ALL_BITS: u32 = 0o7777;
+
+impl PermFilter {
+    /// Parses a permission constraint in octal (`644`) or symbolic (`u+x,g-w`) notation,
+    /// optionally preceded by '-' or '/'.
+    pub fn from_string(input: &str) -> Result<Self> {
+        let (kind, mode) = if let Some(mode) = input.strip_prefix('-') {
+            (PermMatch::AllOf, mode)
+        } else if let Some(mode) = input.strip_prefix('/') {
+            (PermMatch::AnyOf, mode)
+        } else {
+            (PermMatch::Exact, input)
+        };
+
+        let mode = parse_octal(mode)
+            .or_else(|| parse_symbolic(mode))
+            .ok_or_else(|| {
+                anyhow!(
+                    "'{}' is not a valid permission mode. See 'fdx --help'.",
+                    input
+                )
+            })?;
+
+        Ok(PermFilter { mode, kind })
+    }
+
+    pub fn matches(&self, md: &fs::Metadata) -> bool {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = md.permissions().mode() & ALL_BITS;
+        match self.kind {
+            PermMatch::Exact => mode == self.mode,
+            PermMatch::AllOf => mode & self.mode == self.mode,
+            PermMatch::AnyOf => self.mode == 0 || mode & self.mode != 0,
+        }
+    }
+}
+
+fn parse_octal(s: &str) -> Option<u32> {
+    if s.is_empty() || s.len() > 4 || !s.bytes().all(|b| (b'0'..=b'7').contains(&b)) {
+        return None;
+    }
+    u32::from_str_radix(s, 8).ok()
+}
+
+/// Parses a comma-separated list of symbolic modes like `u+x-w,go=r`. As with `find -perm`, the
+/// clauses are applied to an initial mode of zero.
+fn parse_symbolic(s: &str) -> Option<u32> {
+    let mut mode = 0;
+    for clause in s.split(',') {
+        let op_pos = clause.find(['+', '-', '='])?;
+        let (who, rest) = clause.split_at(op_pos);
+
+        let mut who_mask = 0;
+        for c in who.chars() {
+            who_mask |= match c {
+                'u' => 0o4700,
+                'g' => 0o2070,
+                'o' => 0o0007,
+                'a' => 0o6777,
+                _ => return None,
+            };
+        }
+        if who.is_empty() {
+            who_mask = 0o6777;
+        }
+
+        // A clause can hold several operations for the same classes, like `u+x-w`.
+        let mut rest = rest;
+        while let Some(op) = rest.chars().next() {
+            let perms = &rest[1..];
+            let end = perms.find(['+', '-', '=']).unwrap_or(perms.len());
+            let (perms, next) = perms.split_at(end);
+            rest = next;
+
+            let mut perm_bits = 0;
+            for c in perms.chars() {
+                perm_bits |= match c {
+                    'r' => 0o0444,
+                    'w' => 0o0222,
+                    'x' => 0o0111,
+                    's' => 0o6000,
+                    't' => 0o1000,
+                    _ => return None,
+                };
+            }
+            // The sticky bit is not tied to a user class.
+            let bits = (perm_bits & who_mask) | (perm_bits & 0o1000);
+
+            match op {
+                '+' => mode |= bits,
+                '-' => mode &= !bits,
+                '=' => mode = (mode & !who_mask) | bits,
+                _ => unreachable!(),
+            }
+        }
+    }
+    Some(mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> (PermMatch, u32) {
+        let filter = PermFilter::from_string(input).unwrap();
+        (filter.kind, filter.mode)
+    }
+
+    #[test]
+    fn octal_modes() {
+        assert_eq!(parse("644"), (PermMatch::Exact, 0o644));
+        assert_eq!(parse("4755"), (PermMatch::Exact, 0o4755));
+        assert_eq!(parse("-111"), (PermMatch::AllOf, 0o111));
+        assert_eq!(parse("/222"), (PermMatch::AnyOf, 0o222));
+    }
+
+    // The expected values are the ones `find -perm` uses for the same modes.
+    #[test]
+    fn symbolic_modes() {
+        assert_eq!(parse("u+s"), (PermMatch::Exact, 0o4000));
+        assert_eq!(parse("g+s"), (PermMatch::Exact, 0o2000));
+        assert_eq!(parse("o+s"), (PermMatch::Exact, 0));
+        assert_eq!(parse("+t"), (PermMatch::Exact, 0o1000));
+        assert_eq!(parse("u=rwx,go=rx"), (PermMatch::Exact, 0o755));
+        assert_eq!(parse("a-w"), (PermMatch::Exact, 0));
+        assert_eq!(parse("-u+x"), (PermMatch::AllOf, 0o100));
+        assert_eq!(parse("/o+w"), (PermMatch::AnyOf, 0o002));
+        assert_eq!(parse("u+x-w"), (PermMatch::Exact, 0o100));
+        assert_eq!(parse("u=rwx-w,g+r+x"), (PermMatch::Exact, 0o550));
+        assert_eq!(parse("a+rw=r"), (PermMatch::Exact, 0o444));
+    }
+
+    #[test]
+    fn invalid_modes() {
+        for input in ["", "u+x-y", "-", "/", "8", "77777", "u+y", "z+x", "u", "u+x,"] {
+            assert!(PermFilter::from_string(input).is_err(), "{:?}", input);
+        }
+    }
+}
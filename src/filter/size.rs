@@ -0,0 +1,119 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static SIZE_CAPTURES: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^([+-]?)(\d+)(b|[kmgt]i?b?)$").unwrap());
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SizeFilter {
+    Max(u64),
+    Min(u64),
+    Equals(u64),
+}
+
+// SI prefixes (powers of 10)
+const KILO: u64 = 1000;
+const MEGA: u64 = KILO * 1000;
+const GIGA: u64 = MEGA * 1000;
+const TERA: u64 = GIGA * 1000;
+
+// Binary prefixes (powers of 2)
+const KIBI: u64 = 1024;
+const MEBI: u64 = KIBI * 1024;
+const GIBI: u64 = MEBI * 1024;
+const TEBI: u64 = GIBI * 1024;
+
+impl SizeFilter {
+    pub fn from_string(s: &str) -> Option<Self> {
+        let captures = SIZE_CAPTURES.captures(s)?;
+        let limit_kind = captures.get(1).map_or("+", |m| m.as_str());
+        let quantity = captures
+            .get(2)
+            .and_then(|v| v.as_str().parse::<u64>().ok())?;
+
+        let multiplier = match &captures.get(3).map_or("b", |m| m.as_str()).to_lowercase()[..] {
+            v if v.starts_with("ki") => KIBI,
+            v if v.starts_with('k') => KILO,
+            v if v.starts_with("mi") => MEBI,
+            v if v.starts_with('m') => MEGA,
+            v if v.starts_with("gi") => GIBI,
+            v if v.starts_with('g') => GIGA,
+            v if v.starts_with("ti") => TEBI,
+            v if v.starts_with('t') => TERA,
+            "b" => 1,
+            _ => return None,
+        };
+
+        let size = quantity.checked_mul(multiplier)?;
+        match limit_kind {
+            "+" => Some(SizeFilter::Min(size)),
+            "-" => Some(SizeFilter::Max(size)),
+            "" => Some(SizeFilter::Equals(size)),
+            _ => None,
+        }
+    }
+
+    pub fn is_within(&self, size: u64) -> bool {
+        match *self {
+            SizeFilter::Max(limit) => size <= limit,
+            SizeFilter::Min(limit) => size >= limit,
+            SizeFilter::Equals(limit) => size == limit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limit_kinds() {
+        assert_eq!(SizeFilter::from_string("+1k"), Some(SizeFilter::Min(1000)));
+        assert_eq!(SizeFilter::from_string("-1k"), Some(SizeFilter::Max(1000)));
+        assert_eq!(SizeFilter::from_string("1k"), Some(SizeFilter::Equals(1000)));
+    }
+
+    #[test]
+    fn units() {
+        assert_eq!(SizeFilter::from_string("+10b"), Some(SizeFilter::Min(10)));
+        assert_eq!(SizeFilter::from_string("+1kb"), Some(SizeFilter::Min(KILO)));
+        assert_eq!(SizeFilter::from_string("+1ki"), Some(SizeFilter::Min(KIBI)));
+        assert_eq!(SizeFilter::from_string("+1kib"), Some(SizeFilter::Min(KIBI)));
+        assert_eq!(SizeFilter::from_string("+2m"), Some(SizeFilter::Min(2 * MEGA)));
+        assert_eq!(SizeFilter::from_string("+2mi"), Some(SizeFilter::Min(2 * MEBI)));
+        assert_eq!(SizeFilter::from_string("+3g"), Some(SizeFilter::Min(3 * GIGA)));
+        assert_eq!(SizeFilter::from_string("+3gib"), Some(SizeFilter::Min(3 * GIBI)));
+        assert_eq!(SizeFilter::from_string("+4t"), Some(SizeFilter::Min(4 * TERA)));
+        assert_eq!(SizeFilter::from_string("+4ti"), Some(SizeFilter::Min(4 * TEBI)));
+    }
+
+    #[test]
+    fn units_are_case_insensitive() {
+        assert_eq!(SizeFilter::from_string("+1KiB"), Some(SizeFilter::Min(KIBI)));
+        assert_eq!(SizeFilter::from_string("-5MB"), Some(SizeFilter::Max(5 * MEGA)));
+        assert_eq!(SizeFilter::from_string("7B"), Some(SizeFilter::Equals(7)));
+    }
+
+    #[test]
+    fn invalid_sizes() {
+        for input in ["", "+", "1", "+1", "k", "+1x", "+1bb", "++1k", "1.5k", " 1k", "+-1k"] {
+            assert_eq!(SizeFilter::from_string(input), None, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn overflowing_size_is_rejected() {
+        assert_eq!(SizeFilter::from_string("+99999999999999999999b"), None);
+        assert_eq!(SizeFilter::from_string("+18446744073709551615t"), None);
+    }
+
+    #[test]
+    fn is_within() {
+        assert!(SizeFilter::Min(10).is_within(10));
+        assert!(!SizeFilter::Min(10).is_within(9));
+        assert!(SizeFilter::Max(10).is_within(10));
+        assert!(!SizeFilter::Max(10).is_within(11));
+        assert!(SizeFilter::Equals(10).is_within(10));
+        assert!(!SizeFilter::Equals(10).is_within(11));
+    }
+}
@@ -0,0 +1,194 @@
+use anyhow::anyhow;
+use chrono::{offset::TimeZone, DateTime, Local, NaiveDate, NaiveDateTime};
+
+use std::fs::Metadata;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+/// The timestamp of an entry that a time filter is compared against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeKind {
+    /// Last modification time (mtime).
+    Modified,
+    /// Last access time (atime).
+    Accessed,
+    /// Last status change time (ctime).
+    Changed,
+    /// Creation time. On Linux, this is read via `statx` and might not be supported by the
+    /// underlying file system.
+    Birth,
+}
+
+impl FromStr for TimeKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mtime" | "modified" => Ok(TimeKind::Modified),
+            "atime" | "accessed" => Ok(TimeKind::Accessed),
+            "ctime" | "changed" => Ok(TimeKind::Changed),
+            "btime" | "birth" => Ok(TimeKind::Birth),
+            _ => Err(anyhow!(
+                "'{}' is not a valid time kind. See 'fdx --help'.",
+                s
+            )),
+        }
+    }
+}
+
+impl TimeKind {
+    /// Read the corresponding timestamp from the metadata of a file.
+    pub fn read(self, metadata: &Metadata) -> Option<SystemTime> {
+        match self {
+            TimeKind::Modified => metadata.modified().ok(),
+            TimeKind::Accessed => metadata.accessed().ok(),
+            TimeKind::Changed => status_change_time(metadata),
+            TimeKind::Birth => metadata.created().ok(),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn status_change_time(metadata: &Metadata) -> Option<SystemTime> {
+    use std::os::unix::fs::MetadataExt;
+    use std::time::Duration;
+
+    let secs = metadata.ctime();
+    let nanos = metadata.ctime_nsec() as u32;
+    if secs >= 0 {
+        SystemTime::UNIX_EPOCH.checked_add(Duration::new(secs as u64, nanos))
+    } else {
+        SystemTime::UNIX_EPOCH
+            .checked_sub(Duration::new(secs.unsigned_abs(), 0))?
+            .checked_add(Duration::new(0, nanos))
+    }
+}
+
+/// Filter based on time ranges.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TimeFilter {
+    Before(SystemTime),
+    After(SystemTime),
+}
+
+impl TimeFilter {
+    fn from_str(ref_time: &SystemTime, s: &str) -> Option<SystemTime> {
+        humantime::parse_duration(s)
+            .ok()
+            .and_then(|duration| ref_time.checked_sub(duration))
+            .or_else(|| {
+                DateTime::parse_from_rfc3339(s)
+                    .map(|dt| dt.into())
+                    .ok()
+                    .or_else(|| {
+                        NaiveDate::parse_from_str(s, "%F")
+                            .ok()
+                            .and_then(|nd| nd.and_hms_opt(0, 0, 0))
+                            .and_then(|ndt| Local.from_local_datetime(&ndt).single())
+                            .map(|dt| dt.into())
+                    })
+                    .or_else(|| {
+                        NaiveDateTime::parse_from_str(s, "%F %T")
+                            .ok()
+                            .and_then(|ndt| Local.from_local_datetime(&ndt).single())
+                            .map(|dt| dt.into())
+                    })
+            })
+    }
+
+    pub fn before(ref_time: &SystemTime, s: &str) -> Option<TimeFilter> {
+        TimeFilter::from_str(ref_time, s).map(TimeFilter::Before)
+    }
+
+    pub fn after(ref_time: &SystemTime, s: &str) -> Option<TimeFilter> {
+        TimeFilter::from_str(ref_time, s).map(TimeFilter::After)
+    }
+
+    pub fn applies_to(&self, t: &SystemTime) -> bool {
+        match self {
+            TimeFilter::Before(limit) => t < limit,
+            TimeFilter::After(limit) => t > limit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn time_kinds() {
+        assert_eq!("mtime".parse::<TimeKind>().unwrap(), TimeKind::Modified);
+        assert_eq!("accessed".parse::<TimeKind>().unwrap(), TimeKind::Accessed);
+        assert_eq!("ctime".parse::<TimeKind>().unwrap(), TimeKind::Changed);
+        assert_eq!("birth".parse::<TimeKind>().unwrap(), TimeKind::Birth);
+        assert!("".parse::<TimeKind>().is_err());
+        assert!("MTIME".parse::<TimeKind>().is_err());
+    }
+
+    #[test]
+    fn durations_are_relative_to_the_reference_time() {
+        let ref_time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        assert_eq!(
+            TimeFilter::after(&ref_time, "1min"),
+            Some(TimeFilter::After(ref_time - Duration::from_secs(60)))
+        );
+        assert_eq!(
+            TimeFilter::before(&ref_time, "2h 10s"),
+            Some(TimeFilter::Before(ref_time - Duration::from_secs(7210)))
+        );
+        assert_eq!(
+            TimeFilter::after(&ref_time, "1week"),
+            Some(TimeFilter::After(ref_time - Duration::from_secs(7 * 24 * 3600)))
+        );
+    }
+
+    #[test]
+    fn rfc3339_dates() {
+        let ref_time = SystemTime::now();
+        let expected = SystemTime::UNIX_EPOCH + Duration::from_secs(1_286_705_410);
+        assert_eq!(
+            TimeFilter::after(&ref_time, "2010-10-10T10:10:10Z"),
+            Some(TimeFilter::After(expected))
+        );
+        assert_eq!(
+            TimeFilter::after(&ref_time, "2010-10-10T12:10:10+02:00"),
+            Some(TimeFilter::After(expected))
+        );
+    }
+
+    #[test]
+    fn local_dates() {
+        let ref_time = SystemTime::now();
+        let midnight: SystemTime = Local.with_ymd_and_hms(2010, 10, 10, 0, 0, 0).unwrap().into();
+        assert_eq!(
+            TimeFilter::before(&ref_time, "2010-10-10"),
+            Some(TimeFilter::Before(midnight))
+        );
+        let with_time: SystemTime = Local.with_ymd_and_hms(2010, 10, 10, 10, 10, 10).unwrap().into();
+        assert_eq!(
+            TimeFilter::before(&ref_time, "2010-10-10 10:10:10"),
+            Some(TimeFilter::Before(with_time))
+        );
+    }
+
+    #[test]
+    fn invalid_times() {
+        let ref_time = SystemTime::now();
+        for input in ["", "yesterday", "1 lightyear", "2010-13-01", "2010-10-10T10:10", "10:10:10"] {
+            assert_eq!(TimeFilter::after(&ref_time, input), None, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn applies_to() {
+        let limit = SystemTime::UNIX_EPOCH + Duration::from_secs(100);
+        let earlier = limit - Duration::from_secs(1);
+        let later = limit + Duration::from_secs(1);
+        assert!(TimeFilter::Before(limit).applies_to(&earlier));
+        assert!(!TimeFilter::Before(limit).applies_to(&limit));
+        assert!(TimeFilter::After(limit).applies_to(&later));
+        assert!(!TimeFilter::After(limit).applies_to(&limit));
+    }
+}
@@ -0,0 +1,51 @@
+use std::io::{self, IsTerminal};
+use std::os::raw::c_int;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use nix::sys::signal::{raise, signal, SigHandler, Signal};
+use nix::unistd;
+use once_cell::sync::OnceCell;
+
+const STDOUT_FILENO: c_int = 1;
+
+/// The flag that is set by the SIGINT handler.
+static INTERRUPT_FLAG: OnceCell<Arc<AtomicBool>> = OnceCell::new();
+
+/// Whether stdout is a terminal. Determined up front, as the handler can not safely check it.
+static STDOUT_IS_TERMINAL: AtomicBool = AtomicBool::new(false);
+
+/// Install a SIGINT handler that sets `interrupt_flag` on the first ^C, so the search can shut
+/// down cleanly. A second ^C terminates the process immediately.
+pub fn install_handler(interrupt_flag: &Arc<AtomicBool>) {
+    if INTERRUPT_FLAG.set(Arc::clone(interrupt_flag)).is_err() {
+        return;
+    }
+    STDOUT_IS_TERMINAL.store(io::stdout().is_terminal(), Ordering::Relaxed);
+
+    // Safe, because the handler only uses async-signal-safe functions.
+    unsafe {
+        let _ = signal(Signal::SIGINT, SigHandler::Handler(handle_sigint));
+    }
+}
+
+extern "C" fn handle_sigint(_: c_int) {
+    let already_interrupted = INTERRUPT_FLAG
+        .get()
+        .is_none_or(|flag| flag.swap(true, Ordering::Relaxed));
+    if !already_interrupted {
+        return;
+    }
+
+    // ^C has been pressed twice, exit NOW. Output might have been cut off in the middle of an
+    // escape sequence, so reset the terminal style first.
+    if STDOUT_IS_TERMINAL.load(Ordering::Relaxed) {
+        let _ = unistd::write(STDOUT_FILENO, b"\x1b[0m");
+    }
+    unsafe {
+        if signal(Signal::SIGINT, SigHandler::SigDfl).is_ok() {
+            let _ = raise(Signal::SIGINT);
+        }
+    }
+}
+
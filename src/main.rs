@@ -1,20 +1,44 @@
-use std::{env, path::{Path, PathBuf}, sync::Arc};
+use std::{env, fs, io::{self, IsTerminal}, path::{Path, PathBuf}, sync::Arc, time};
 
 use anyhow::{Result,anyhow, Context};
+use config::{Config, SymlinkPolicy};
 use error::print_error;
+use filetypes::FileTypes;
+#[cfg(unix)]
+use filter::{OwnerFilter, PermFilter};
+use filter::{SizeFilter, TimeFilter, TimeKind};
 use exit_codes::ExitCode;
 use globset::GlobBuilder;
+use lscolors::LsColors;
 use normpath::PathExt;
-use regex::bytes::RegexBuilder;
+use regex::bytes::{RegexBuilder, RegexSetBuilder};
+use regex_helper::pattern_has_uppercase_char;
 mod dir_entry;
 
 mod app;
 mod error;
 mod exit_codes;
 mod filesystem;
+mod filetypes;
+mod filter;
+#[cfg(unix)]
+mod interrupt;
 mod config;
 mod walk;
 mod output;
+mod regex_helper;
+
+/// The color theme that is used if `LS_COLORS` is not set.
+const DEFAULT_LS_COLORS: &str = "\
+di=01;34:ln=01;36:or=01;31:mi=01;31:so=01;35:pi=33:ex=01;32:bd=01;33:cd=01;33:\
+su=37;41:sg=30;43:tw=30;42:ow=34;42:st=37;44:\
+*.7z=01;31:*.bz2=01;31:*.gz=01;31:*.rar=01;31:*.tar=01;31:*.tgz=01;31:*.xz=01;31:\
+*.zip=01;31:*.zst=01;31:\
+*.bmp=01;35:*.gif=01;35:*.jpeg=01;35:*.jpg=01;35:*.png=01;35:*.svg=01;35:*.webp=01;35:\
+*.flac=00;36:*.mp3=00;36:*.ogg=00;36:*.wav=00;36:\
+*.avi=01;35:*.mkv=01;35:*.mov=01;35:*.mp4=01;35:*.webm=01;35:\
+*.md=00;33:*.rst=00;33:*.txt=00;33:*README=00;33:*README.md=00;33:\
+*.bak=00;90:*.lock=00;90:*.log=00;90:*.swp=00;90:*.tmp=00;90:*~=00;90";
 
 fn main() {
     let result = run();
@@ -31,15 +55,14 @@ fn main() {
 
 fn run() -> Result<ExitCode> {
     let matches = app::build_app().get_matches_from(env::args_os());
-    set_working_dir(&matches);
+    set_working_dir(&matches)?;
     let pattern = extract_search_pattern(&matches)?;
-    println!("{}", pattern);
-    // ensure_search_pattern_is_not_a_path(&matches, pattern)?;
+    ensure_search_pattern_is_not_a_path(&matches, pattern)?;
     let pattern_regex = build_pattern_regex(&matches, pattern)?;
-    let re = build_regex(pattern_regex)?;
-    let search_paths = extract_search_paths(&matches)?;
-    println!("{:?}", search_paths);
-    walk::scan(&search_paths, Arc::new(re))
+    let config = construct_config(&matches, &pattern_regex)?;
+    let re = build_regex(pattern_regex, &config)?;
+    let search_paths = extract_search_paths(&matches, &config)?;
+    walk::scan(&search_paths, Arc::new(re), Arc::new(config))
 }
 
 fn set_working_dir(matches: &clap::ArgMatches) -> Result<()> {
@@ -73,16 +96,23 @@ fn extract_search_pattern(matches: &clap::ArgMatches) -> Result<&'_ str> {
     Ok(pattern)
 }
 
-fn extract_search_paths(matches: &clap::ArgMatches) -> Result<Vec<PathBuf>> {
+fn extract_search_paths(matches: &clap::ArgMatches, config: &Config) -> Result<Vec<PathBuf>> {
     let parameter_paths = matches
         .values_of_os("path")
         .or_else(|| matches.values_of_os("search-path"));
 
-    let mut search_paths = match parameter_paths {
+    let search_paths = match parameter_paths {
         Some(paths) => paths
             .filter_map(|path| {
                 let path_buffer = PathBuf::from(path);
-                if filesystem::is_existing_directory(&path_buffer) {
+                if config.symlink_policy == SymlinkPolicy::Never && path_buffer.is_symlink() {
+                    print_error(format!(
+                        "Search path '{}' is a symbolic link and will not be followed \
+                         (see '--follow-roots' and '--follow').",
+                        path_buffer.to_string_lossy(),
+                    ));
+                    None
+                } else if filesystem::is_existing_directory(&path_buffer) {
                     Some(path_buffer)
                 } else {
                     print_error(format!(
@@ -102,6 +132,7 @@ fn extract_search_paths(matches: &clap::ArgMatches) -> Result<Vec<PathBuf>> {
     if search_paths.is_empty() {
         return Err(anyhow!("No valid search paths given."));
     };
+    let mut search_paths = filesystem::dedup_search_paths(search_paths);
     if matches.is_present("absolute-path") {
         update_to_absolute_paths(&mut search_paths);
     }
@@ -135,9 +166,9 @@ fn ensure_search_pattern_is_not_a_path(matches: &clap::ArgMatches, pattern: &str
             "The search pattern '{pattern}' contains a path-separation character ('{sep}') \
              and will not lead to any search results.\n\n\
              If you want to search for all files inside the '{pattern}' directory, use a match-all pattern:\n\n  \
-             fd . '{pattern}'\n\n\
+             fdx . '{pattern}'\n\n\
              Instead, if you want your pattern to match the full file path, use:\n\n  \
-             fd --full-path '{pattern}'",
+             fdx --full-path '{pattern}'",
             pattern = pattern,
             sep = std::path::MAIN_SEPARATOR,
         ))
@@ -150,14 +181,281 @@ fn build_pattern_regex(matches: &clap::ArgMatches, pattern: &str) -> Result<Stri
     Ok(if matches.is_present("glob") && !pattern.is_empty() {
         let glob = GlobBuilder::new(pattern).literal_separator(true).build()?;
         glob.regex().to_owned()
+    } else if matches.is_present("fixed-strings") {
+        // Treat pattern as literal string if '--fixed-strings' is used
+        regex::escape(pattern)
     } else {
         String::from(pattern)
     })
 }
 
-fn build_regex(pattern_regex: String) -> Result<regex::bytes::Regex> {
+fn construct_config(matches: &clap::ArgMatches, pattern_regex: &str) -> Result<Config> {
+    // The search will be case-sensitive if the command line flag is set or
+    // if the pattern has an uppercase character (smart case).
+    let case_sensitive = !matches.is_present("ignore-case")
+        && (matches.is_present("case-sensitive") || pattern_has_uppercase_char(pattern_regex));
+
+    let interactive_terminal = io::stdout().is_terminal();
+    let colored_output = match matches.value_of("color") {
+        Some("always") => true,
+        Some("never") => false,
+        _ => env::var_os("NO_COLOR").is_none() && interactive_terminal,
+    };
+    let ls_colors = if colored_output {
+        Some(LsColors::from_env().unwrap_or_else(|| LsColors::from_string(DEFAULT_LS_COLORS)))
+    } else {
+        None
+    };
+
+    let path_separator = matches.value_of("path-separator").map(String::from);
+    if path_separator.as_deref() == Some("") {
+        return Err(anyhow!("The '--path-separator' can not be empty."));
+    }
+    let actual_path_separator = path_separator
+        .clone()
+        .unwrap_or_else(|| std::path::MAIN_SEPARATOR.to_string());
+
+    // The './' prefix only shows up for the implicit search path, so only strip it there.
+    let strip_cwd_prefix = !matches.is_present("path")
+        && !matches.is_present("search-path")
+        && match matches.value_of("strip-cwd-prefix") {
+            Some("always") => true,
+            Some("never") => false,
+            _ => interactive_terminal,
+        };
+
+    let time_kind = matches
+        .value_of("time-kind")
+        .map(str::parse)
+        .transpose()?
+        .unwrap_or(TimeKind::Modified);
+
+    Ok(Config {
+        case_sensitive,
+        search_full_path: matches.is_present("full-path"),
+        ignore_hidden: !(matches.is_present("hidden")
+            || matches.occurrences_of("rg-alias-hidden-ignore") >= 2),
+        read_ignore: !(matches.is_present("no-ignore")
+            || matches.is_present("rg-alias-hidden-ignore")),
+        read_parent_ignore: !matches.is_present("no-ignore-parent"),
+        read_vcsignore: !(matches.is_present("no-ignore")
+            || matches.is_present("rg-alias-hidden-ignore")
+            || matches.is_present("no-ignore-vcs")),
+        require_git_to_read_vcsignore: !matches.is_present("no-require-git"),
+        read_global_ignore: !(matches.is_present("no-ignore")
+            || matches.is_present("rg-alias-hidden-ignore")
+            || matches.is_present("no-global-ignore-file")),
+        symlink_policy: if matches.is_present("follow") {
+            SymlinkPolicy::Always
+        } else if matches.is_present("no-follow") {
+            SymlinkPolicy::Never
+        } else {
+            SymlinkPolicy::Roots
+        },
+        ls_colors,
+        interactive_terminal,
+        null_separator: matches.is_present("null_separator"),
+        max_depth: matches
+            .value_of("max-depth")
+            .or_else(|| matches.value_of("exact-depth"))
+            .map(|n| n.parse::<usize>())
+            .transpose()
+            .context("Failed to parse argument to --max-depth/--exact-depth")?,
+        min_depth: matches
+            .value_of("min-depth")
+            .or_else(|| matches.value_of("exact-depth"))
+            .map(|n| n.parse::<usize>())
+            .transpose()
+            .context("Failed to parse argument to --min-depth/--exact-depth")?,
+        prune: matches.is_present("prune"),
+        threads: matches
+            .value_of("threads")
+            .map(|n| n.parse::<usize>())
+            .transpose()
+            .context("Failed to parse number of threads")?
+            .map(|n| {
+                if n > 0 {
+                    Ok(n)
+                } else {
+                    Err(anyhow!("Number of threads must be positive."))
+                }
+            })
+            .transpose()?
+            .unwrap_or_else(default_num_threads),
+        path_separator,
+        actual_path_separator,
+        strip_cwd_prefix,
+        quiet: matches.is_present("quiet"),
+        max_results: matches
+            .value_of("max-results")
+            .map(|n| n.parse::<usize>())
+            .transpose()
+            .context("Failed to parse --max-results argument")?
+            .filter(|&n| n > 0)
+            .or_else(|| {
+                if matches.is_present("max-one-result") {
+                    Some(1)
+                } else {
+                    None
+                }
+            }),
+        broken_symlinks_only: matches.is_present("broken"),
+        file_types: matches.values_of("file-type").map(|values| {
+            let mut file_types = FileTypes::default();
+            for value in values {
+                match value {
+                    "f" | "file" => file_types.files = true,
+                    "d" | "directory" => file_types.directories = true,
+                    "l" | "symlink" => file_types.symlinks = true,
+                    "x" | "executable" => {
+                        file_types.executables_only = true;
+                        file_types.files = true;
+                    }
+                    "e" | "empty" => file_types.empty_only = true,
+                    "s" | "socket" => file_types.sockets = true,
+                    "p" | "pipe" => file_types.pipes = true,
+                    "b" | "block-device" => file_types.block_devices = true,
+                    "c" | "char-device" => file_types.char_devices = true,
+                    _ => unreachable!(),
+                }
+            }
+
+            // If only 'empty' was specified, search for both files and directories:
+            if file_types.empty_only && !(file_types.files || file_types.directories) {
+                file_types.files = true;
+                file_types.directories = true;
+            }
+
+            file_types
+        }),
+        extensions: matches
+            .values_of("extension")
+            .map(|exts| {
+                let patterns = exts
+                    .map(|e| e.trim_start_matches('.'))
+                    .map(|e| format!(r".\.{}$", regex::escape(e)));
+                RegexSetBuilder::new(patterns)
+                    .case_insensitive(true)
+                    .build()
+            })
+            .transpose()?,
+        exclude_patterns: extract_exclude_patterns(matches)?,
+        ignore_files: matches
+            .values_of_os("ignore-file")
+            .map(|vs| vs.map(PathBuf::from).collect())
+            .unwrap_or_default(),
+        size_constraints: extract_size_limits(matches)?,
+        time_constraints: extract_time_constraints(matches, time_kind)?,
+        time_kind,
+        #[cfg(unix)]
+        owner_constraint: if let Some(s) = matches.value_of("owner") {
+            OwnerFilter::from_string(s)?
+        } else {
+            None
+        },
+        #[cfg(unix)]
+        perm_constraint: matches
+            .value_of("perm")
+            .map(PermFilter::from_string)
+            .transpose()?,
+    })
+}
+
+fn extract_time_constraints(
+    matches: &clap::ArgMatches,
+    time_kind: TimeKind,
+) -> Result<Vec<TimeFilter>> {
+    let now = time::SystemTime::now();
+    let mut time_constraints: Vec<TimeFilter> = Vec::new();
+    if let Some(t) = matches.value_of("changed-within") {
+        if let Some(f) = TimeFilter::after(&now, t) {
+            time_constraints.push(f);
+        } else {
+            return Err(anyhow!(
+                "'{}' is not a valid date or duration. See 'fdx --help'.",
+                t
+            ));
+        }
+    }
+    if let Some(t) = matches.value_of("changed-before") {
+        if let Some(f) = TimeFilter::before(&now, t) {
+            time_constraints.push(f);
+        } else {
+            return Err(anyhow!(
+                "'{}' is not a valid date or duration. See 'fdx --help'.",
+                t
+            ));
+        }
+    }
+    if let Some(reference) = matches.value_of_os("newer") {
+        let reference = Path::new(reference);
+        let metadata = fs::metadata(reference).with_context(|| {
+            format!(
+                "Could not read the reference file '{}'",
+                reference.to_string_lossy()
+            )
+        })?;
+        let reference_time = time_kind.read(&metadata).ok_or_else(|| {
+            anyhow!(
+                "The reference file '{}' does not provide the requested timestamp.",
+                reference.to_string_lossy()
+            )
+        })?;
+        time_constraints.push(TimeFilter::After(reference_time));
+    }
+    Ok(time_constraints)
+}
+
+fn extract_size_limits(matches: &clap::ArgMatches) -> Result<Vec<SizeFilter>> {
+    matches.values_of("size").map_or(Ok(Vec::new()), |vs| {
+        vs.map(|sf| {
+            SizeFilter::from_string(sf)
+                .ok_or_else(|| anyhow!("'{}' is not a valid size constraint. See 'fdx --help'.", sf))
+        })
+        .collect::<Result<Vec<_>>>()
+    })
+}
+
+fn extract_exclude_patterns(matches: &clap::ArgMatches) -> Result<Vec<String>> {
+    let mut patterns: Vec<String> = matches
+        .values_of("exclude")
+        .map(|v| v.map(String::from).collect())
+        .unwrap_or_default();
+
+    if let Some(files) = matches.values_of_os("exclude-from") {
+        for file in files {
+            let content = fs::read_to_string(file).with_context(|| {
+                format!(
+                    "Could not read exclude patterns from '{}'",
+                    Path::new(file).to_string_lossy()
+                )
+            })?;
+            patterns.extend(
+                content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(String::from),
+            );
+        }
+    }
+
+    // Overrides are whitelists by default, a leading '!' turns them into ignore globs.
+    Ok(patterns.into_iter().map(|p| String::from("!") + &p).collect())
+}
+
+/// The number of logical CPUs, limited by the CPU quota of the cgroup we are running in.
+fn default_num_threads() -> usize {
+    let num_cpus = std::thread::available_parallelism().map_or(1, |n| n.get());
+    match filesystem::cgroup_cpu_limit() {
+        Some(limit) => num_cpus.min(limit).max(1),
+        None => num_cpus,
+    }
+}
+
+fn build_regex(pattern_regex: String, config: &Config) -> Result<regex::bytes::Regex> {
     RegexBuilder::new(&pattern_regex)
-        .case_insensitive(false)
+        .case_insensitive(!config.case_sensitive)
         .dot_matches_new_line(true)
         .build()
         .map_err(|e| {
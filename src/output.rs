@@ -1,12 +1,16 @@
 use std::io::{Write, self};
 
-use lscolors::Style;
+use lscolors::{Indicator, LsColors, Style};
 
 use crate::{dir_entry::DirEntry, config::Config, error::print_error, exit_codes::ExitCode};
 
 
-pub fn print_entry<W: Write>(stdout: &mut W, entry: &DirEntry) {
-    let r = print_entry_uncolorized(stdout, entry);
+pub fn print_entry<W: Write>(stdout: &mut W, entry: &DirEntry, config: &Config) {
+    let r = if let Some(ref ls_colors) = config.ls_colors {
+        print_entry_colorized(stdout, entry, config, ls_colors)
+    } else {
+        print_entry_uncolorized(stdout, entry, config)
+    };
     if let Err(e) = r {
         if e.kind() == ::std::io::ErrorKind::BrokenPipe {
             // Exit gracefully in case of a broken pipe (e.g. 'fd ... | head -n 3').
@@ -18,21 +22,56 @@ pub fn print_entry<W: Write>(stdout: &mut W, entry: &DirEntry) {
     }
 }
 
+fn print_entry_colorized<W: Write>(
+    stdout: &mut W,
+    entry: &DirEntry,
+    config: &Config,
+    ls_colors: &LsColors,
+) -> io::Result<()> {
+    let separator = if config.null_separator { "\0" } else { "\n" };
+    let path = entry.stripped_path(config);
+
+    // Traverse the path and colorize each component. The style of the last component is based
+    // on the (cached) metadata of the entry itself.
+    let mut components = ls_colors.style_for_path_components(path).peekable();
+    while let Some((component, style)) = components.next() {
+        let style = if components.peek().is_none() {
+            ls_colors.style_for_path_with_metadata(path, entry.metadata())
+        } else {
+            style
+        };
+        let style = style.map(Style::to_ansi_term_style).unwrap_or_default();
+        let mut component_string = component.to_string_lossy();
+        if let Some(ref separator) = config.path_separator {
+            *component_string.to_mut() = replace_path_separator(&component_string, separator);
+        }
+        write!(stdout, "{}", style.paint(component_string))?;
+    }
+
+    print_trailing_slash(
+        stdout,
+        entry,
+        config,
+        ls_colors.style_for_indicator(Indicator::Directory),
+    )?;
+    write!(stdout, "{}", separator)
+}
 
 // TODO: this function is performance critical and can probably be optimized
 fn print_entry_uncolorized_base<W: Write>(
     stdout: &mut W,
     entry: &DirEntry,
+    config: &Config,
 ) -> io::Result<()> {
-    let separator =  "\n";
-    let path = entry.stripped_path();
+    let separator = if config.null_separator { "\0" } else { "\n" };
+    let path = entry.stripped_path(config);
 
     let mut path_string = path.to_string_lossy();
-    // if let Some(ref separator) = config.path_separator {
-    //     *path_string.to_mut() = replace_path_separator(&path_string, separator);
-    // }
+    if let Some(ref separator) = config.path_separator {
+        *path_string.to_mut() = replace_path_separator(&path_string, separator);
+    }
     write!(stdout, "{}", path_string)?;
-    print_trailing_slash(stdout, entry, None)?;
+    print_trailing_slash(stdout, entry, config, None)?;
     write!(stdout, "{}", separator)
 }
 
@@ -40,36 +79,63 @@ fn print_entry_uncolorized_base<W: Write>(
 fn print_trailing_slash<W: Write>(
     stdout: &mut W,
     entry: &DirEntry,
+    config: &Config,
     style: Option<&Style>,
 ) -> io::Result<()> {
-    if entry.file_type().map_or(false, |ft| ft.is_dir()) {
+    if entry.file_type().is_some_and(|ft| ft.is_dir()) {
         write!(
             stdout,
             "{}",
             style
                 .map(Style::to_ansi_term_style)
                 .unwrap_or_default()
-                .paint(std::path::MAIN_SEPARATOR.to_string())
+                .paint(&config.actual_path_separator)
         )?;
     }
     Ok(())
 }
 
+/// Replace the OS-specific path separator with a custom one.
+fn replace_path_separator(path: &str, new_path_separator: &str) -> String {
+    path.replace(std::path::MAIN_SEPARATOR, new_path_separator)
+}
+
 #[cfg(unix)]
 fn print_entry_uncolorized<W: Write>(
     stdout: &mut W,
     entry: &DirEntry,
+    config: &Config,
 ) -> io::Result<()> {
+    use std::os::unix::ffi::OsStrExt;
 
-    print_entry_uncolorized_base(stdout, entry)
-    // if config.interactive_terminal || config.path_separator.is_some() {
-    //     // Fall back to the base implementation
-    //     print_entry_uncolorized_base(stdout, entry, config)
-    // } else {
-    //     // Print path as raw bytes, allowing invalid UTF-8 filenames to be passed to other processes
-    //     let separator = if config.null_separator { b"\0" } else { b"\n" };
-    //     stdout.write_all(entry.stripped_path(config).as_os_str().as_bytes())?;
-    //     print_trailing_slash(stdout, entry, config, None)?;
-    //     stdout.write_all(separator)
-    // }
+    if config.interactive_terminal {
+        // Fall back to the base implementation
+        print_entry_uncolorized_base(stdout, entry, config)
+    } else {
+        // Print path as raw bytes, allowing invalid UTF-8 filenames to be passed to other processes
+        let separator = if config.null_separator { b"\0" } else { b"\n" };
+        let path_bytes = entry.stripped_path(config).as_os_str().as_bytes();
+        match config.path_separator {
+            Some(ref path_separator) => {
+                for (i, part) in path_bytes.split(|&b| b == b'/').enumerate() {
+                    if i > 0 {
+                        stdout.write_all(path_separator.as_bytes())?;
+                    }
+                    stdout.write_all(part)?;
+                }
+            }
+            None => stdout.write_all(path_bytes)?,
+        }
+        print_trailing_slash(stdout, entry, config, None)?;
+        stdout.write_all(separator)
+    }
+}
+
+#[cfg(not(unix))]
+fn print_entry_uncolorized<W: Write>(
+    stdout: &mut W,
+    entry: &DirEntry,
+    config: &Config,
+) -> io::Result<()> {
+    print_entry_uncolorized_base(stdout, entry, config)
 }
@@ -0,0 +1,81 @@
+use regex_syntax::ast::{self, Ast};
+
+/// Determine if a regex pattern contains a literal uppercase character.
+pub fn pattern_has_uppercase_char(pattern: &str) -> bool {
+    let mut parser = ast::parse::Parser::new();
+
+    parser
+        .parse(pattern)
+        .map(|ast| ast_has_uppercase_char(&ast))
+        .unwrap_or(false)
+}
+
+/// Determine if a regex expression contains a literal uppercase character.
+///
+/// Only characters the user actually wrote are considered. Class escapes like `\W` or `\pL`
+/// contain uppercase characters as well, but they do not count.
+fn ast_has_uppercase_char(ast: &Ast) -> bool {
+    match *ast {
+        Ast::Literal(ref literal) => literal.c.is_uppercase(),
+        Ast::Class(ast::Class::Bracketed(ref class)) => class_set_has_uppercase_char(&class.kind),
+        Ast::Group(ast::Group { ref ast, .. })
+        | Ast::Repetition(ast::Repetition { ref ast, .. }) => ast_has_uppercase_char(ast),
+        Ast::Concat(ast::Concat { ref asts, .. })
+        | Ast::Alternation(ast::Alternation { ref asts, .. }) => {
+            asts.iter().any(ast_has_uppercase_char)
+        }
+        _ => false,
+    }
+}
+
+fn class_set_has_uppercase_char(set: &ast::ClassSet) -> bool {
+    match *set {
+        ast::ClassSet::Item(ref item) => class_set_item_has_uppercase_char(item),
+        ast::ClassSet::BinaryOp(ref op) => {
+            class_set_has_uppercase_char(&op.lhs) || class_set_has_uppercase_char(&op.rhs)
+        }
+    }
+}
+
+fn class_set_item_has_uppercase_char(item: &ast::ClassSetItem) -> bool {
+    use regex_syntax::ast::ClassSetItem;
+
+    match *item {
+        ClassSetItem::Literal(ref literal) => literal.c.is_uppercase(),
+        ClassSetItem::Range(ref range) => {
+            range.start.c.is_uppercase() || range.end.c.is_uppercase()
+        }
+        ClassSetItem::Bracketed(ref class) => class_set_has_uppercase_char(&class.kind),
+        ClassSetItem::Union(ref union) => union.items.iter().any(class_set_item_has_uppercase_char),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pattern_has_uppercase_char;
+
+    #[test]
+    fn class_escapes_are_not_uppercase() {
+        assert!(!pattern_has_uppercase_char(r"readme\S"));
+        assert!(!pattern_has_uppercase_char(r"readme\W"));
+        assert!(!pattern_has_uppercase_char(r"readme\D"));
+        assert!(!pattern_has_uppercase_char(r"\pL\PN[[:upper:]]"));
+    }
+
+    #[test]
+    fn written_uppercase_chars() {
+        assert!(pattern_has_uppercase_char("Foo"));
+        assert!(pattern_has_uppercase_char("[A-Z]"));
+        assert!(pattern_has_uppercase_char("[a-cX]"));
+        assert!(pattern_has_uppercase_char("(foo|Bar)+"));
+        assert!(pattern_has_uppercase_char("[[a-z]&&[^Q]]"));
+    }
+
+    #[test]
+    fn lowercase_patterns() {
+        assert!(!pattern_has_uppercase_char("foo"));
+        assert!(!pattern_has_uppercase_char("[a-z]+.rs$"));
+        assert!(!pattern_has_uppercase_char(""));
+    }
+}
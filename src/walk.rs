@@ -1,18 +1,20 @@
-use std::{path::PathBuf, sync::{mpsc::{channel, Receiver, RecvTimeoutError, Sender}, Arc, atomic::{AtomicBool, Ordering}}, time::{Instant, Duration}, io::{Write, self}, mem, thread, ffi::OsStr, borrow::Cow};
+use std::{collections::{HashMap, HashSet}, path::{Path, PathBuf}, sync::{mpsc::{channel, Receiver, RecvTimeoutError, Sender}, Arc, Mutex, PoisonError, atomic::{AtomicBool, Ordering}}, time::{Instant, Duration}, io::{Write, self}, mem, thread, ffi::OsStr, borrow::Cow};
 
-use ignore::{overrides::OverrideBuilder, WalkBuilder};
+use ignore::{overrides::{Override, OverrideBuilder}, WalkBuilder};
 use anyhow::{anyhow, Result};
 use regex::bytes::Regex;
 
 
-use crate::{exit_codes::ExitCode, dir_entry::DirEntry, error::print_error, output, filesystem};
+use crate::{config::{Config, SymlinkPolicy}, exit_codes::ExitCode, dir_entry::DirEntry, error::print_error, output, filesystem};
+#[cfg(unix)]
+use crate::interrupt;
 
 /// Default duration until output buffering switches to streaming.
 pub const DEFAULT_MAX_BUFFER_TIME: Duration = Duration::from_millis(100);
 /// Maximum size of the output buffer before flushing results to the console
 pub const MAX_BUFFER_LENGTH: usize = 1000;
 
-pub fn scan(path_vec: &[PathBuf], pattern: Arc<Regex>) -> Result<ExitCode> {
+pub fn scan(path_vec: &[PathBuf], pattern: Arc<Regex>, config: Arc<Config>) -> Result<ExitCode> {
     let mut path_iter = path_vec.iter();
     let first_path_buf = path_iter
         .next()
@@ -20,31 +22,41 @@ pub fn scan(path_vec: &[PathBuf], pattern: Arc<Regex>) -> Result<ExitCode> {
     let (tx, rx) = channel();
 
     let mut override_builder = OverrideBuilder::new(first_path_buf.as_path());
+    for pattern in &config.exclude_patterns {
+        override_builder
+            .add(pattern)
+            .map_err(|e| anyhow!("Malformed exclude pattern: {}", e))?;
+    }
     let overrides = override_builder
         .build()
         .map_err(|_| anyhow!("Mismatch in exclude patterns"))?;
     let mut walker = WalkBuilder::new(first_path_buf.as_path());
+    for path_entry in path_iter {
+        walker.add(path_entry.as_path());
+    }
+    for message in configure_walker(&mut walker, &config, overrides.clone()) {
+        print_error(message);
+    }
+    let roots = SearchRoots::new(Arc::clone(&config), path_vec, overrides);
     walker
-        .hidden(true)
-        .ignore(false)
-        .parents(true)
-        .git_ignore(true)
-        .git_global(true)
-        .git_exclude(true)
-        .overrides(overrides)
-        .follow_links(true);
-
-    let parallel_walker = walker.threads(4).build_parallel();
+        // Search paths are always followed by the walker, which already gives us the behavior of
+        // `SymlinkPolicy::Roots`. Symlinked roots are filtered out earlier for `Never`.
+        .follow_links(config.symlink_policy == SymlinkPolicy::Always)
+        .max_depth(config.max_depth);
+
+    let parallel_walker = walker.threads(config.threads).build_parallel();
     // Flag for cleanly shutting down the parallel walk
     let quit_flag = Arc::new(AtomicBool::new(false));
     // Flag specifically for quitting due to ^C
     let interrupt_flag = Arc::new(AtomicBool::new(false));
+    #[cfg(unix)]
+    interrupt::install_handler(&interrupt_flag);
 
     // Spawn the thread that receives all results through the channel.
-    let receiver_thread = spawn_receiver(&quit_flag, &interrupt_flag, rx);
+    let receiver_thread = spawn_receiver(&config, &quit_flag, &interrupt_flag, rx);
 
     // Spawn the sender threads.
-    spawn_senders(&quit_flag,  pattern, parallel_walker, tx);
+    spawn_senders(&config, &quit_flag, &interrupt_flag, pattern, roots, parallel_walker, tx);
 
     // Wait for the receiver thread to print out all results.
     let exit_code = receiver_thread.join().unwrap();
@@ -55,6 +67,193 @@ pub fn scan(path_vec: &[PathBuf], pattern: Arc<Regex>) -> Result<ExitCode> {
     }
 }
 
+/// Applies the hidden, ignore and exclude settings of the config to a walker.
+///
+/// Malformed ignore files are returned as error messages instead of being printed, so that the
+/// caller decides whether to report them.
+fn configure_walker(walker: &mut WalkBuilder, config: &Config, overrides: Override) -> Vec<String> {
+    let mut errors = Vec::new();
+    walker
+        .hidden(config.ignore_hidden)
+        .ignore(config.read_ignore)
+        .parents(config.read_parent_ignore)
+        .git_ignore(config.read_vcsignore)
+        .git_global(config.read_vcsignore && config.read_global_ignore)
+        .git_exclude(config.read_vcsignore)
+        .require_git(config.require_git_to_read_vcsignore)
+        .overrides(overrides);
+
+    if config.read_ignore {
+        walker.add_custom_ignore_filename(".fdxignore");
+    }
+
+    if config.read_global_ignore {
+        if let Some(global_ignore_file) = filesystem::config_dir()
+            .map(|p| p.join("fdx").join("ignore"))
+            .filter(|p| p.is_file())
+        {
+            let result = walker.add_ignore(global_ignore_file);
+            match result {
+                Some(ignore::Error::Partial(_)) => (),
+                Some(err) => {
+                    errors.push(format!("Malformed pattern in global ignore file. {}.", err));
+                }
+                None => (),
+            }
+        }
+    }
+
+    for ignore_file in &config.ignore_files {
+        let result = walker.add_ignore(ignore_file);
+        match result {
+            Some(ignore::Error::Partial(_)) => (),
+            Some(err) => {
+                errors.push(format!("Malformed pattern in custom ignore file. {}.", err));
+            }
+            None => (),
+        }
+    }
+
+    errors
+}
+
+/// The search paths of a walk, as far as the sender threads need to know about them.
+///
+/// Nested search paths are walked as roots of their own, so the walk of an outer root must not
+/// descend into them.
+///
+/// When following links, the walker reports a dangling link as an error before any ignore or
+/// exclude rule has been applied to it. The directory containing the link is therefore walked to
+/// once more from its search path with the same rules (without following links), and the visible
+/// entries are cached per directory.
+struct SearchRoots {
+    config: Arc<Config>,
+    paths: Vec<PathBuf>,
+    nested: Vec<PathBuf>,
+    overrides: Override,
+    visible: Mutex<HashMap<PathBuf, Arc<HashSet<PathBuf>>>>,
+}
+
+impl SearchRoots {
+    fn new(config: Arc<Config>, paths: &[PathBuf], overrides: Override) -> Self {
+        Self {
+            config,
+            paths: paths.to_vec(),
+            nested: filesystem::nested_search_paths(paths),
+            overrides,
+            visible: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether the entry is a directory that is also a search path of its own.
+    fn is_nested_root(&self, entry: &DirEntry) -> bool {
+        !self.nested.is_empty()
+            && entry.file_type().is_some_and(|ft| ft.is_dir())
+            && self.nested.iter().any(|nested| nested == entry.path())
+    }
+
+    /// Turns a dangling link into an entry, unless the ignore rules hide it.
+    fn broken_symlink(&self, path: PathBuf) -> Option<DirEntry> {
+        if self.is_ignored(&path) {
+            return None;
+        }
+        let depth = self.depth(&path);
+        Some(DirEntry::broken_symlink(path, depth))
+    }
+
+    fn is_ignored(&self, path: &Path) -> bool {
+        let parent = match path.parent() {
+            Some(parent) => parent,
+            None => return false,
+        };
+        // The cache only ever grows, so it is still usable if another thread panicked.
+        let cached = self
+            .visible
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(parent)
+            .cloned();
+        let visible = match cached {
+            Some(visible) => visible,
+            None => {
+                // List the directory without holding the lock, so that other threads are not
+                // blocked. Two threads might list the same directory, which is harmless.
+                let listed = Arc::new(self.list_visible(parent));
+                Arc::clone(
+                    self.visible
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .entry(parent.to_path_buf())
+                        .or_insert(listed),
+                )
+            }
+        };
+        !visible.contains(path)
+    }
+
+    /// The search path a dangling link was found in.
+    fn root_of(&self, path: &Path) -> Option<&Path> {
+        // Nested search paths are not descended into by outer roots, so the longest matching
+        // root is the one the link was found in.
+        self.paths
+            .iter()
+            .filter(|root| path.starts_with(root))
+            .max_by_key(|root| root.components().count())
+            .map(PathBuf::as_path)
+    }
+
+    /// The depth of a dangling link below the search path it was found in. The walker does not
+    /// report it along with the error.
+    fn depth(&self, path: &Path) -> usize {
+        self.root_of(path)
+            .and_then(|root| path.strip_prefix(root).ok())
+            .map_or(1, |relative| relative.components().count())
+    }
+
+    fn list_visible(&self, dir: &Path) -> HashSet<PathBuf> {
+        // Walking down from the search path applies the ignore files of all directories in
+        // between, independent of `read_parent_ignore`. That only fails if one of them is a
+        // symlink, which is not followed here. The directory is then listed on its own, with the
+        // ignore files of all its (resolved) parent directories.
+        self.root_of(dir)
+            .and_then(|root| self.list_visible_from(root, dir, self.config.read_parent_ignore))
+            .unwrap_or_else(|| self.list_visible_from(dir, dir, true).unwrap_or_default())
+    }
+
+    /// Walks from `root` down to `dir` and returns the visible entries of `dir`, or `None` if
+    /// `dir` was not reached.
+    fn list_visible_from(
+        &self,
+        root: &Path,
+        dir: &Path,
+        read_parent_ignore: bool,
+    ) -> Option<HashSet<PathBuf>> {
+        let dir_depth = dir.strip_prefix(root).ok()?.components().count();
+        let target = dir.to_path_buf();
+        let mut walker = WalkBuilder::new(root);
+        // Errors in ignore files have already been reported by the main walker.
+        configure_walker(&mut walker, &self.config, self.overrides.clone());
+        walker
+            .parents(read_parent_ignore)
+            .follow_links(false)
+            .max_depth(Some(dir_depth + 1))
+            .filter_entry(move |entry| {
+                target.starts_with(entry.path()) || entry.path().parent() == Some(&target)
+            });
+
+        let mut reached = dir_depth == 0;
+        let mut visible = HashSet::new();
+        for entry in walker.build().filter_map(|entry| entry.ok()) {
+            if entry.depth() == dir_depth + 1 {
+                visible.insert(entry.into_path());
+            } else if entry.path() == dir && entry.file_type().is_some_and(|ft| ft.is_dir()) {
+                reached = true;
+            }
+        }
+        reached.then_some(visible)
+    }
+}
+
 #[derive(PartialEq)]
 enum ReceiverMode {
     /// Receiver is still buffering in order to sort the results, if the search finishes fast
@@ -66,13 +265,40 @@ enum ReceiverMode {
 }
 
 pub enum WorkerResult {
-    Entry(DirEntry),
+    // Boxed, as a `DirEntry` is much larger than an error.
+    Entry(Box<DirEntry>),
     Error(ignore::Error),
 }
 
+/// Turn an error from the walker into a message for the user.
+///
+/// Symlink loops are reported with both ends of the loop, everything else uses the error text
+/// of the `ignore` crate.
+fn format_walk_error(err: &ignore::Error) -> String {
+    fn find_loop(err: &ignore::Error) -> Option<(&Path, &Path)> {
+        match err {
+            ignore::Error::Loop { ancestor, child } => Some((ancestor, child)),
+            ignore::Error::WithDepth { err, .. }
+            | ignore::Error::WithPath { err, .. }
+            | ignore::Error::WithLineNumber { err, .. } => find_loop(err),
+            ignore::Error::Partial(errs) => errs.iter().find_map(find_loop),
+            _ => None,
+        }
+    }
+
+    match find_loop(err) {
+        Some((ancestor, child)) => format!(
+            "Symlink loop: '{}' points to its ancestor directory '{}' and will not be followed.",
+            child.to_string_lossy(),
+            ancestor.to_string_lossy()
+        ),
+        None => err.to_string(),
+    }
+}
+
 struct ReceiverBuffer<W> {
     /// The configuration.
-    // config: Arc<Config>,
+    config: Arc<Config>,
     /// For shutting down the senders.
     quit_flag: Arc<AtomicBool>,
     /// The ^C notifier.
@@ -93,7 +319,7 @@ struct ReceiverBuffer<W> {
 
 impl<W: Write> ReceiverBuffer<W> {
     fn new(
-        // config: Arc<Config>,
+        config: Arc<Config>,
         quit_flag: Arc<AtomicBool>,
         interrupt_flag: Arc<AtomicBool>,
         rx: Receiver<WorkerResult>,
@@ -103,7 +329,7 @@ impl<W: Write> ReceiverBuffer<W> {
         let deadline = Instant::now() + max_buffer_time;
 
         Self {
-            // config,
+            config,
             quit_flag,
             interrupt_flag,
             rx,
@@ -125,15 +351,24 @@ impl<W: Write> ReceiverBuffer<W> {
     }
 
     fn poll(&mut self) -> Result<(), ExitCode> {
+        if self.interrupt_flag.load(Ordering::Relaxed) {
+            // Print the results we already have, but nothing after that.
+            if self.mode == ReceiverMode::Buffering {
+                self.buffer.sort();
+            }
+            self.stream()?;
+            return Err(ExitCode::KilledBySigint);
+        }
+
         match self.recv() {
             Ok(WorkerResult::Entry(dir_entry)) => {
-                // if self.config.quiet {
-                //     return Err(ExitCode::HasResults(true));
-                // }
+                if self.config.quiet {
+                    return Err(ExitCode::HasResults(true));
+                }
 
                 match self.mode {
                     ReceiverMode::Buffering => {
-                        self.buffer.push(dir_entry);
+                        self.buffer.push(*dir_entry);
                         if self.buffer.len() > MAX_BUFFER_LENGTH {
                             self.stream()?;
                         }
@@ -145,14 +380,14 @@ impl<W: Write> ReceiverBuffer<W> {
                 }
 
                 self.num_results += 1;
-                // if let Some(max_results) = self.config.max_results {
-                //     if self.num_results >= max_results {
-                //         return self.stop();
-                //     }
-                // }
+                if let Some(max_results) = self.config.max_results {
+                    if self.num_results >= max_results {
+                        return self.stop();
+                    }
+                }
             }
             Ok(WorkerResult::Error(err)) => {
-                print_error(err.to_string());
+                print_error(format_walk_error(&err));
                 // if self.config.show_filesystem_errors {
                 //     print_error(err.to_string());
                 // }
@@ -196,7 +431,7 @@ impl<W: Write> ReceiverBuffer<W> {
     }
 
     fn print(&mut self, entry: &DirEntry) -> Result<(), ExitCode>{
-        output::print_entry(&mut self.stdout, entry);
+        output::print_entry(&mut self.stdout, entry, &self.config);
         Ok(())
     }
 
@@ -206,67 +441,98 @@ impl<W: Write> ReceiverBuffer<W> {
             self.buffer.sort();
             self.stream()?;
         }
-        Err(ExitCode::HasResults(self.num_results > 0))
-        // if self.config.quiet {
-        //     Err(ExitCode::HasResults(self.num_results > 0))
-        // } else {
-        //     Err(ExitCode::Success)
-        // }
+        if self.config.quiet {
+            Err(ExitCode::HasResults(self.num_results > 0))
+        } else {
+            Err(ExitCode::Success)
+        }
     }
 
     /// Flush stdout if necessary.
     fn flush(&mut self) -> Result<(), ExitCode> {
-        if self.stdout.flush().is_err() {
+        if self.config.interactive_terminal && self.stdout.flush().is_err() {
+            // Probably a broken pipe. Exit gracefully.
             return Err(ExitCode::GeneralError);
         }
-        // if self.config.interactive_terminal && self.stdout.flush().is_err() {
-        //     // Probably a broken pipe. Exit gracefully.
-        //     return Err(ExitCode::GeneralError);
-        // }
         Ok(())
     }
 
 }
 
 fn spawn_receiver(
-    // config: &Arc<Config>,
+    config: &Arc<Config>,
     quit_flag: &Arc<AtomicBool>,
     interrupt_flag: &Arc<AtomicBool>,
     rx: Receiver<WorkerResult>,
 ) -> thread::JoinHandle<ExitCode> {
-    // let configs = Arc::clone(config);
+    let config = Arc::clone(config);
     let quit_flag = Arc::clone(quit_flag);
     let interrupt_flag = Arc::clone(interrupt_flag);
 
-    let threads = 4;
     thread::spawn(move || {
         let stdout = io::stdout();
         let stdout = stdout.lock();
         let stdout = io::BufWriter::new(stdout);
-        let mut rxbuffer = ReceiverBuffer::new(quit_flag, interrupt_flag, rx, stdout);
+        let mut rxbuffer = ReceiverBuffer::new(config, quit_flag, interrupt_flag, rx, stdout);
             rxbuffer.process()
     })
 }
 
 fn spawn_senders(
-    // config: &Arc<Config>,
+    config: &Arc<Config>,
     quit_flag: &Arc<AtomicBool>,
+    interrupt_flag: &Arc<AtomicBool>,
     pattern: Arc<Regex>,
+    roots: SearchRoots,
     parallel_walker: ignore::WalkParallel,
     tx: Sender<WorkerResult>,
 )  {
+    let roots = Arc::new(roots);
     parallel_walker.run(|| {
-        // let config = Arc::clone(config);
+        let config = Arc::clone(config);
+        let roots = Arc::clone(&roots);
+        let quit_flag = Arc::clone(quit_flag);
+        let interrupt_flag = Arc::clone(interrupt_flag);
         let pattern = Arc::clone(&pattern);
         let tx_thread = tx.clone();
-        let quit_flag = Arc::clone(quit_flag);
         Box::new(move | entry_o| {
+            // Stop the walk as soon as the receiver is done or ^C has been pressed.
+            if quit_flag.load(Ordering::Relaxed) || interrupt_flag.load(Ordering::Relaxed) {
+                return ignore::WalkState::Quit;
+            }
+
             let entry = match entry_o {
                 Ok(ref e) if e.depth() == 0 => {
                     // Skip the root directory entry.
                     return ignore::WalkState::Continue;
                 }
                 Ok(e) => DirEntry::normal(e),
+                // When following links, the walker reports dangling links as 'not found' errors.
+                Err(ignore::Error::WithPath {
+                    path,
+                    err: inner_err,
+                }) => match inner_err.as_ref() {
+                    ignore::Error::Io(io_error)
+                        if io_error.kind() == io::ErrorKind::NotFound
+                            && path
+                                .symlink_metadata()
+                                .is_ok_and(|m| m.file_type().is_symlink()) =>
+                    {
+                        match roots.broken_symlink(path) {
+                            Some(entry) => entry,
+                            None => return ignore::WalkState::Continue,
+                        }
+                    }
+                    _ => {
+                        return match tx_thread.send(WorkerResult::Error(ignore::Error::WithPath {
+                            path,
+                            err: inner_err,
+                        })) {
+                            Ok(_) => ignore::WalkState::Continue,
+                            Err(_) => ignore::WalkState::Quit,
+                        }
+                    }
+                },
                 Err(err) => {
                     return match tx_thread.send(WorkerResult::Error(err)) {
                         Ok(_) => ignore::WalkState::Continue,
@@ -274,23 +540,141 @@ fn spawn_senders(
                     }
                 }
             };
-            let entry_path = entry.path();
-            let search_str: Cow<OsStr> =  match entry_path.file_name() {
-                Some(filename) => Cow::Borrowed(filename),
-                None => unreachable!(
-                    "Encountered file system entry without a file name. This should only \
-                     happen for paths like 'foo/bar/..' or '/' which are not supposed to \
-                     appear in a file system traversal."
-                ),
-            };
-            if !pattern.is_match(&filesystem::osstr_to_bytes(search_str.as_ref())) {
-                return ignore::WalkState::Continue;
-            }
-            let send_result = tx_thread.send(WorkerResult::Entry(entry));
-            if send_result.is_err() {
-                return ignore::WalkState::Quit;
+            // Nested search paths are walked as roots of their own, so the walk of an outer root
+            // only reports the directory itself and does not descend into it.
+            let is_nested_root = roots.is_nested_root(&entry);
+
+            match process_entry(&config, &pattern, &tx_thread, entry) {
+                ignore::WalkState::Continue if is_nested_root => ignore::WalkState::Skip,
+                state => state,
             }
-            ignore::WalkState::Continue
         })
     })
 }
+
+/// Applies all filters to a single entry and sends it to the receiver if it matches.
+fn process_entry(
+    config: &Config,
+    pattern: &Regex,
+    tx: &Sender<WorkerResult>,
+    entry: DirEntry,
+) -> ignore::WalkState {
+    if let Some(min_depth) = config.min_depth {
+        if entry.depth().is_none_or(|d| d < min_depth) {
+            return ignore::WalkState::Continue;
+        }
+    }
+
+    let entry_path = entry.path();
+    let search_str: Cow<OsStr> = if config.search_full_path {
+        // Search paths are already absolute if '--absolute-path' is used, so the
+        // entry path only needs its leading './' removed.
+        Cow::Borrowed(filesystem::strip_current_dir(entry_path).as_os_str())
+    } else {
+        match entry_path.file_name() {
+            Some(filename) => Cow::Borrowed(filename),
+            None => unreachable!(
+                "Encountered file system entry without a file name. This should only \
+                 happen for paths like 'foo/bar/..' or '/' which are not supposed to \
+                 appear in a file system traversal."
+            ),
+        }
+    };
+    if !pattern.is_match(&filesystem::osstr_to_bytes(search_str.as_ref())) {
+        return ignore::WalkState::Continue;
+    }
+    // Filter out unwanted extensions.
+    if let Some(ref exts_regex) = config.extensions {
+        if let Some(path_str) = entry_path.file_name() {
+            if !exts_regex.is_match(&filesystem::osstr_to_bytes(path_str)) {
+                return ignore::WalkState::Continue;
+            }
+        } else {
+            return ignore::WalkState::Continue;
+        }
+    }
+
+    // Filter out symlinks whose target exists.
+    if config.broken_symlinks_only && !entry.is_broken_symlink() {
+        return ignore::WalkState::Continue;
+    }
+
+    // Filter out unwanted file types.
+    if let Some(ref file_types) = config.file_types {
+        if file_types.should_ignore(&entry) {
+            return ignore::WalkState::Continue;
+        }
+    }
+
+    // Filter out unwanted sizes if it is a file and we have been given size constraints.
+    if !config.size_constraints.is_empty() {
+        if entry.file_type().is_some_and(|ft| ft.is_file()) {
+            if let Some(metadata) = entry.metadata() {
+                let file_size = metadata.len();
+                if config
+                    .size_constraints
+                    .iter()
+                    .any(|sc| !sc.is_within(file_size))
+                {
+                    return ignore::WalkState::Continue;
+                }
+            } else {
+                return ignore::WalkState::Continue;
+            }
+        } else {
+            return ignore::WalkState::Continue;
+        }
+    }
+
+    // Filter out unwanted timestamps
+    if !config.time_constraints.is_empty() {
+        let mut matched = false;
+        if let Some(metadata) = entry.metadata() {
+            if let Some(file_time) = config.time_kind.read(metadata) {
+                matched = config
+                    .time_constraints
+                    .iter()
+                    .all(|tf| tf.applies_to(&file_time));
+            }
+        }
+        if !matched {
+            return ignore::WalkState::Continue;
+        }
+    }
+
+    #[cfg(unix)]
+    {
+        if let Some(ref owner_constraint) = config.owner_constraint {
+            if let Some(metadata) = entry.metadata() {
+                if !owner_constraint.matches(metadata) {
+                    return ignore::WalkState::Continue;
+                }
+            } else {
+                return ignore::WalkState::Continue;
+            }
+        }
+
+        if let Some(ref perm_constraint) = config.perm_constraint {
+            if let Some(metadata) = entry.metadata() {
+                if !perm_constraint.matches(metadata) {
+                    return ignore::WalkState::Continue;
+                }
+            } else {
+                return ignore::WalkState::Continue;
+            }
+        }
+    }
+
+    let send_result = tx.send(WorkerResult::Entry(Box::new(entry)));
+    if send_result.is_err() {
+        return ignore::WalkState::Quit;
+    }
+
+    // Apply pruning. Only entries that passed all filters above get here, so this never
+    // skips directories that were rejected by a type filter or a depth limit.
+    if config.prune {
+        return ignore::WalkState::Skip;
+    }
+
+    ignore::WalkState::Continue
+}